@@ -0,0 +1,76 @@
+//! Suggested repo path: examples/full_pipeline.rs
+//!
+//! A runnable tour of the whole symbolic layer: seed a `MemoryGraph`, drive
+//! a `KurisuShell` conversation over it, track a small goal hierarchy and
+//! check its alignment, and attempt a proof with whatever provers happen
+//! to be installed. Run with `cargo run --example full_pipeline`.
+
+use std::collections::HashMap;
+
+use fractal_amadeus::goal_tracker::{Goal, GoalTracker, GoalType};
+use fractal_amadeus::kurisu_shell::KurisuShell;
+use fractal_amadeus::memory_graph::{MemoryGraph, SymbolicNode};
+use fractal_amadeus::proof_engine::ProofEngine;
+
+fn main() {
+    let mut graph = MemoryGraph::new();
+    graph.add_concept(SymbolicNode {
+        key: "divergence meter".to_string(),
+        content: "measures the world-line's divergence number".to_string(),
+        confidence: 0.95,
+        framework: "sci-fi".to_string(),
+        last_updated: chrono::Utc::now(),
+        provenance: Some("manual entry".to_string()),
+        metadata: HashMap::new(),
+    });
+    graph.add_concept(SymbolicNode {
+        key: "time leap machine".to_string(),
+        content: "sends memories back in time".to_string(),
+        confidence: 0.9,
+        framework: "sci-fi".to_string(),
+        last_updated: chrono::Utc::now(),
+        provenance: Some("manual entry".to_string()),
+        metadata: HashMap::new(),
+    });
+
+    let shell = KurisuShell::new();
+    for input in [
+        "Okabe checks the Divergence Meter",
+        "the Time Leap Machine only sends memories, not matter",
+    ] {
+        let (response, confidence) = shell.process_input(input, &graph);
+        println!("> {input}");
+        println!("  {response} (confidence: {confidence:?})");
+    }
+
+    let mut goals = GoalTracker::new();
+    goals.add_goal(Goal {
+        id: "become-neuroscientist".to_string(),
+        description: "become a world-class neuroscientist".to_string(),
+        goal_type: GoalType::Terminal,
+        tags: Vec::new(),
+        confidence: 1.0,
+        priority: 0,
+        due_at: None,
+    }).expect("valid confidence");
+    goals.add_goal(Goal {
+        id: "read-daily".to_string(),
+        description: "read one paper a day".to_string(),
+        goal_type: GoalType::Tactical,
+        tags: Vec::new(),
+        confidence: 1.0,
+        priority: 0,
+        due_at: None,
+    }).expect("valid confidence");
+    goals
+        .relate_goals("read-daily", "become-neuroscientist", 0.3)
+        .expect("both goals exist");
+
+    println!("\nAlignment drift (threshold 0.5): {:?}", goals.detect_alignment_drift(0.5));
+
+    let engine = ProofEngine::with_auto_detected_provers();
+    match engine.verify_with_any_prover("forall n, n + 0 = n") {
+        Ok(status) => println!("\nProof status: {status:?}"),
+        Err(e) => println!("\nNo provers available to attempt a proof ({e}); skipping."),
+    }
+}
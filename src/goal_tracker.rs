@@ -0,0 +1,1527 @@
+//! Suggested repo path: src/goal_tracker.rs
+//!
+//! Tracks Kurisu's goals (terminal and tactical) and the relations between
+//! them, so we can reason about alignment: whether tactical goals still
+//! support the terminal ones they were derived from.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GoalType {
+    Terminal,
+    Tactical,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Goal {
+    pub id: String,
+    pub description: String,
+    pub goal_type: GoalType,
+    /// Free-form labels (e.g. "safety", "research", "infra"). Defaults to
+    /// empty so goals serialized before this field existed still load.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// How confident we are that this goal is worth pursuing, in `0.0..=1.0`.
+    /// Defaults to 1.0 so goals serialized before this field existed still
+    /// load as fully confident.
+    #[serde(default = "default_goal_confidence")]
+    pub confidence: f32,
+    /// How urgently this goal should be worked on relative to others -
+    /// higher comes first in `GoalTracker::goals_by_priority`. Defaults to
+    /// 0 so goals serialized before this field existed still load as
+    /// lowest priority.
+    #[serde(default)]
+    pub priority: u8,
+    /// When this goal is due, if there's a deadline. Defaults to `None` so
+    /// goals serialized before this field existed still load.
+    #[serde(default)]
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+fn default_goal_confidence() -> f32 {
+    1.0
+}
+
+impl Goal {
+    /// Start building a `Goal`: `id` is required and must be set via
+    /// `.id(..)` before `build`, `goal_type` defaults to `Tactical`,
+    /// `tags` defaults to empty, `confidence` defaults to `0.5`,
+    /// `priority` defaults to `0`, and `due_at` defaults to `None`.
+    /// Shorter-lived than spelling out every field at each call site.
+    pub fn builder() -> GoalBuilder {
+        GoalBuilder {
+            id: None,
+            description: String::new(),
+            goal_type: GoalType::Tactical,
+            tags: Vec::new(),
+            confidence: 0.5,
+            priority: 0,
+            due_at: None,
+        }
+    }
+}
+
+/// Builder for `Goal`; see `Goal::builder`.
+pub struct GoalBuilder {
+    id: Option<String>,
+    description: String,
+    goal_type: GoalType,
+    tags: Vec<String>,
+    confidence: f32,
+    priority: u8,
+    due_at: Option<DateTime<Utc>>,
+}
+
+impl GoalBuilder {
+    pub fn id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    pub fn type_(mut self, goal_type: GoalType) -> Self {
+        self.goal_type = goal_type;
+        self
+    }
+
+    pub fn tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    pub fn confidence(mut self, confidence: f32) -> Self {
+        self.confidence = confidence;
+        self
+    }
+
+    pub fn priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn due_at(mut self, due_at: DateTime<Utc>) -> Self {
+        self.due_at = Some(due_at);
+        self
+    }
+
+    /// Finish building. `id` is the only required field - everything
+    /// else has a sensible default - so this is the only way `build`
+    /// fails.
+    pub fn build(self) -> Result<Goal, String> {
+        let id = self.id.ok_or("a goal requires an id")?;
+        Ok(Goal {
+            id,
+            description: self.description,
+            goal_type: self.goal_type,
+            tags: self.tags,
+            confidence: self.confidence,
+            priority: self.priority,
+            due_at: self.due_at,
+        })
+    }
+}
+
+/// A directed link from one goal to another (e.g. "this tactical goal
+/// supports that terminal goal"), weighted by how strongly it supports it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalRelation {
+    pub from_id: String,
+    pub to_id: String,
+    pub strength: f32,
+    /// How the relation affects alignment, e.g. "supports" or
+    /// "undermines". Defaults to "supports" so data saved before this
+    /// field existed still loads sensibly.
+    #[serde(default = "default_relation_type")]
+    pub relation_type: String,
+}
+
+fn default_relation_type() -> String {
+    "supports".to_string()
+}
+
+/// The result of following a tactical goal's support chain up to a
+/// terminal goal; see `GoalTracker::drift_report`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DriftReport {
+    /// The tactical goal this report is about.
+    pub goal_id: String,
+    /// The full chain of goal ids, from `goal_id` to the terminal goal it
+    /// reaches, inclusive of both ends.
+    pub path: Vec<String>,
+    /// The weakest `(from_id, to_id, weighted_strength)` edge along `path`.
+    pub weakest_edge: (String, String, f32),
+    /// The product of every edge's weighted strength along `path` - low
+    /// if any single edge is weak.
+    pub alignment: f32,
+}
+
+/// Escape a string for use inside a double-quoted DOT identifier or label:
+/// backslashes first (so the next pass doesn't double-escape them), then
+/// double quotes.
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// How much a relation type counts towards alignment: positive types
+/// reinforce support, negative types (like "undermines") count against it.
+fn relation_type_weight(relation_type: &str) -> f32 {
+    match relation_type {
+        "undermines" => -1.0,
+        _ => 1.0,
+    }
+}
+
+/// An audit-trail entry for a single mutation of a `GoalTracker`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GoalEvent {
+    GoalAdded {
+        id: String,
+        timestamp: DateTime<Utc>,
+    },
+    GoalRemoved {
+        id: String,
+        timestamp: DateTime<Utc>,
+    },
+    RelationAdded {
+        from_id: String,
+        to_id: String,
+        timestamp: DateTime<Utc>,
+    },
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GoalTracker {
+    pub goals: HashMap<String, Goal>,
+    pub relations: Vec<GoalRelation>,
+    /// Off by default; enabled with `set_event_logging(true)`.
+    #[serde(default)]
+    event_logging_enabled: bool,
+    #[serde(default)]
+    events: Vec<GoalEvent>,
+}
+
+impl GoalTracker {
+    pub fn new() -> Self {
+        Self {
+            goals: HashMap::new(),
+            relations: Vec::new(),
+            event_logging_enabled: false,
+            events: Vec::new(),
+        }
+    }
+
+    /// Turn the mutation event log on or off.
+    pub fn set_event_logging(&mut self, enabled: bool) {
+        self.event_logging_enabled = enabled;
+    }
+
+    pub fn events(&self) -> &[GoalEvent] {
+        &self.events
+    }
+
+    pub fn clear_events(&mut self) {
+        self.events.clear();
+    }
+
+    fn record_event(&mut self, event: GoalEvent) {
+        if self.event_logging_enabled {
+            self.events.push(event);
+        }
+    }
+
+    /// Add `goal`. `confidence` must be a finite value in `[0.0, 1.0]` -
+    /// anything else would silently corrupt alignment math downstream, so
+    /// it's rejected here instead, same as `relate_goals`'s strength.
+    pub fn add_goal(&mut self, goal: Goal) -> Result<(), String> {
+        if goal.confidence.is_nan() {
+            return Err("goal confidence must not be NaN".to_string());
+        }
+        if !(0.0..=1.0).contains(&goal.confidence) {
+            return Err(format!("goal confidence {} must be within [0.0, 1.0]", goal.confidence));
+        }
+
+        let id = goal.id.clone();
+        self.goals.insert(id.clone(), goal);
+        self.record_event(GoalEvent::GoalAdded {
+            id,
+            timestamp: Utc::now(),
+        });
+        Ok(())
+    }
+
+    /// Remove a goal by id, returning it if it existed.
+    pub fn remove_goal(&mut self, id: &str) -> Option<Goal> {
+        let removed = self.goals.remove(id);
+        if removed.is_some() {
+            self.record_event(GoalEvent::GoalRemoved {
+                id: id.to_string(),
+                timestamp: Utc::now(),
+            });
+        }
+        removed
+    }
+
+    /// Relate two existing goals with a support `strength`. The strength
+    /// must be a finite value in `[0.0, 1.0]` - anything else (NaN or out
+    /// of range) would silently corrupt drift detection downstream, so we
+    /// reject it here instead.
+    pub fn relate_goals(&mut self, from_id: &str, to_id: &str, strength: f32) -> Result<(), String> {
+        self.relate_goals_typed(from_id, to_id, strength, "supports")
+    }
+
+    /// Like `relate_goals`, but with an explicit relation type (e.g.
+    /// "supports" or "undermines") that `detect_alignment_drift` weighs
+    /// the relation by.
+    pub fn relate_goals_typed(
+        &mut self,
+        from_id: &str,
+        to_id: &str,
+        strength: f32,
+        relation_type: &str,
+    ) -> Result<(), String> {
+        if !self.goals.contains_key(from_id) {
+            return Err(format!("no goal with id '{from_id}'"));
+        }
+        if !self.goals.contains_key(to_id) {
+            return Err(format!("no goal with id '{to_id}'"));
+        }
+        if strength.is_nan() {
+            return Err("goal relation strength must not be NaN".to_string());
+        }
+        if !(0.0..=1.0).contains(&strength) {
+            return Err(format!(
+                "goal relation strength {strength} must be within [0.0, 1.0]"
+            ));
+        }
+
+        self.relations.push(GoalRelation {
+            from_id: from_id.to_string(),
+            to_id: to_id.to_string(),
+            strength,
+            relation_type: relation_type.to_string(),
+        });
+        self.record_event(GoalEvent::RelationAdded {
+            from_id: from_id.to_string(),
+            to_id: to_id.to_string(),
+            timestamp: Utc::now(),
+        });
+        Ok(())
+    }
+
+    /// Flag tactical goals whose weighted support for the terminal goals
+    /// they relate to has dropped below `threshold`. Each relation's
+    /// strength is weighted by its type - an "undermines" relation counts
+    /// against alignment instead of for it. Returns `(goal_id,
+    /// min_weighted_strength)` for every tactical goal whose minimum
+    /// weighted strength across its relations falls below the threshold.
+    pub fn detect_alignment_drift(&self, threshold: f32) -> Vec<(String, f32)> {
+        let mut min_strength: HashMap<&str, f32> = HashMap::new();
+        for relation in &self.relations {
+            if self.goals.get(&relation.from_id).map(|g| &g.goal_type) != Some(&GoalType::Tactical) {
+                continue;
+            }
+            let weighted = relation.strength * relation_type_weight(&relation.relation_type);
+            let entry = min_strength.entry(relation.from_id.as_str()).or_insert(f32::MAX);
+            if weighted < *entry {
+                *entry = weighted;
+            }
+        }
+
+        let mut drifting: Vec<(String, f32)> = min_strength
+            .into_iter()
+            .filter(|&(_, strength)| strength < threshold)
+            .map(|(id, strength)| (id.to_string(), strength))
+            .collect();
+        drifting.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal).then_with(|| a.0.cmp(&b.0)));
+        drifting
+    }
+
+    /// Like `detect_alignment_drift`, but for every tactical goal that has
+    /// a path up to a terminal goal rather than just flagging the ones
+    /// below a threshold: the full chain of goal ids from the tactical
+    /// goal to the terminal it reaches, the weakest edge along that chain,
+    /// and the overall alignment (the product of every edge's weighted
+    /// strength along the chain - low if any single edge is weak, just
+    /// like `transitive_strength`). When a tactical goal has more than one
+    /// parent at a step, parents are tried in lexicographic id order and
+    /// the first chain that reaches a terminal is reported, so the result
+    /// is deterministic. Tactical goals with no path to a terminal are
+    /// omitted.
+    pub fn drift_report(&self) -> Vec<DriftReport> {
+        let mut reports = Vec::new();
+        let mut tactical_ids: Vec<&str> = self
+            .goals
+            .values()
+            .filter(|g| g.goal_type == GoalType::Tactical)
+            .map(|g| g.id.as_str())
+            .collect();
+        tactical_ids.sort();
+
+        for goal_id in tactical_ids {
+            if let Some(path) = self.find_path_to_terminal(goal_id) {
+                let mut weakest: Option<(String, String, f32)> = None;
+                let mut alignment = 1.0;
+                for window in path.windows(2) {
+                    let (from_id, to_id) = (&window[0], &window[1]);
+                    let Some(relation) = self.relations.iter().find(|r| &r.from_id == from_id && &r.to_id == to_id) else {
+                        continue;
+                    };
+                    let weighted = relation.strength * relation_type_weight(&relation.relation_type);
+                    alignment *= weighted;
+                    if weakest.as_ref().is_none_or(|(_, _, w)| weighted < *w) {
+                        weakest = Some((from_id.clone(), to_id.clone(), weighted));
+                    }
+                }
+                if let Some(weakest_edge) = weakest {
+                    reports.push(DriftReport {
+                        goal_id: goal_id.to_string(),
+                        path,
+                        weakest_edge,
+                        alignment,
+                    });
+                }
+            }
+        }
+        reports
+    }
+
+    /// DFS from `goal_id` following `get_parents`, trying parents in
+    /// lexicographic id order and backtracking from dead ends, until a
+    /// terminal goal is reached. Returns the full id path (inclusive of
+    /// both ends), or `None` if no terminal is reachable.
+    fn find_path_to_terminal(&self, goal_id: &str) -> Option<Vec<String>> {
+        let mut stack: Vec<Vec<String>> = vec![vec![goal_id.to_string()]];
+        while let Some(path) = stack.pop() {
+            let current = path.last().expect("paths are never empty");
+            let Some(goal) = self.goals.get(current) else { continue };
+            if goal.goal_type == GoalType::Terminal {
+                return Some(path);
+            }
+            // Pushed largest-first so the smallest parent is popped first.
+            let mut parents = self.get_parents(current);
+            parents.sort_by(|a, b| b.id.cmp(&a.id));
+            for parent in parents.into_iter().filter(|p| !path.contains(&p.id)) {
+                let mut next = path.clone();
+                next.push(parent.id.clone());
+                stack.push(next);
+            }
+        }
+        None
+    }
+
+    /// The maximum-product strength along any path of relations from
+    /// `from_id` to `to_id`, or `None` if there's no such path. Used to
+    /// assess how well a deep tactical goal transitively supports a
+    /// distant terminal goal.
+    pub fn transitive_strength(&self, from_id: &str, to_id: &str) -> Option<f32> {
+        if from_id == to_id {
+            return Some(1.0);
+        }
+
+        // Bellman-Ford-style relaxation: goal graphs are small, and this
+        // handles cycles correctly (unlike plain DFS) since a cycle can
+        // only ever shrink the product, never help it.
+        let mut best: HashMap<&str, f32> = HashMap::new();
+        best.insert(from_id, 1.0);
+
+        for _ in 0..self.goals.len() {
+            let mut changed = false;
+            for relation in &self.relations {
+                if let Some(&from_best) = best.get(relation.from_id.as_str()) {
+                    let candidate = from_best * relation.strength;
+                    let entry = best.entry(relation.to_id.as_str()).or_insert(f32::MIN);
+                    if candidate > *entry {
+                        *entry = candidate;
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        best.get(to_id).copied()
+    }
+
+    /// All the longest chains of goals connected by relations (following
+    /// relation direction), e.g. to spot how deep the tactical support for
+    /// a terminal goal actually runs. Returns every chain tied for the
+    /// maximum depth, each as a sequence of goal ids from start to end.
+    pub fn deepest_goal_chains(&self) -> Vec<Vec<String>> {
+        let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+        for relation in &self.relations {
+            adjacency
+                .entry(relation.from_id.as_str())
+                .or_default()
+                .push(relation.to_id.as_str());
+        }
+
+        let mut all_chains: Vec<Vec<String>> = Vec::new();
+        for start in self.goals.keys() {
+            let start = start.as_str();
+            let mut visiting = std::collections::HashSet::new();
+            self.collect_chains(start, &adjacency, &mut vec![start.to_string()], &mut visiting, &mut all_chains);
+        }
+
+        let max_len = all_chains.iter().map(|c| c.len()).max().unwrap_or(0);
+        let mut deepest: Vec<Vec<String>> = all_chains.into_iter().filter(|c| c.len() == max_len).collect();
+        deepest.sort();
+        deepest.dedup();
+        deepest
+    }
+
+    fn collect_chains<'a>(
+        &'a self,
+        current: &'a str,
+        adjacency: &HashMap<&'a str, Vec<&'a str>>,
+        path: &mut Vec<String>,
+        visiting: &mut std::collections::HashSet<&'a str>,
+        out: &mut Vec<Vec<String>>,
+    ) {
+        let neighbors = adjacency.get(current);
+        match neighbors {
+            None => out.push(path.clone()),
+            Some(neighbors) if neighbors.is_empty() => out.push(path.clone()),
+            Some(neighbors) => {
+                if !visiting.insert(current) {
+                    // Cycle - stop here rather than looping forever.
+                    out.push(path.clone());
+                    return;
+                }
+                for &next in neighbors {
+                    path.push(next.to_string());
+                    self.collect_chains(next, adjacency, path, visiting, out);
+                    path.pop();
+                }
+                visiting.remove(current);
+            }
+        }
+    }
+
+    /// All goals carrying `tag`.
+    pub fn goals_with_tag(&self, tag: &str) -> Vec<&Goal> {
+        self.goals.values().filter(|g| g.tags.iter().any(|t| t == tag)).collect()
+    }
+
+    /// Add `tag` to a goal's tags, deduplicating. Errors if the goal
+    /// doesn't exist.
+    pub fn add_tag(&mut self, goal_id: &str, tag: &str) -> Result<(), String> {
+        let goal = self
+            .goals
+            .get_mut(goal_id)
+            .ok_or_else(|| format!("no goal with id '{goal_id}'"))?;
+        if !goal.tags.iter().any(|t| t == tag) {
+            goal.tags.push(tag.to_string());
+        }
+        Ok(())
+    }
+
+    /// Remove `tag` from a goal's tags, if present. Errors if the goal
+    /// doesn't exist.
+    pub fn remove_tag(&mut self, goal_id: &str, tag: &str) -> Result<(), String> {
+        let goal = self
+            .goals
+            .get_mut(goal_id)
+            .ok_or_else(|| format!("no goal with id '{goal_id}'"))?;
+        goal.tags.retain(|t| t != tag);
+        Ok(())
+    }
+
+    /// Every relation involving `id`, on either end. Mirrors
+    /// `MemoryGraph::get_relationships_for_concept`.
+    pub fn relations_for_goal(&self, id: &str) -> Vec<&GoalRelation> {
+        self.relations.iter().filter(|r| r.from_id == id || r.to_id == id).collect()
+    }
+
+    /// The goals that directly relate to `id` (i.e. support or otherwise
+    /// target it) - its children in the goal hierarchy.
+    pub fn get_children(&self, id: &str) -> Vec<&Goal> {
+        self.relations
+            .iter()
+            .filter(|r| r.to_id == id)
+            .filter_map(|r| self.goals.get(&r.from_id))
+            .collect()
+    }
+
+    /// Every goal reachable by repeatedly following `get_children` from
+    /// `id` - its children, grandchildren, and so on, with each distinct
+    /// descendant appearing once even if it's reachable through more than
+    /// one parent. Guarded against cycles: a goal already visited
+    /// anywhere in the traversal is not re-expanded.
+    pub fn get_descendants(&self, id: &str) -> Vec<Goal> {
+        let mut visited = HashSet::new();
+        let mut out = Vec::new();
+        self.collect_descendants(id, &mut visited, &mut out);
+        out
+    }
+
+    fn collect_descendants(&self, id: &str, visited: &mut HashSet<String>, out: &mut Vec<Goal>) {
+        if !visited.insert(id.to_string()) {
+            return;
+        }
+        for child in self.get_children(id) {
+            if visited.contains(&child.id) {
+                continue;
+            }
+            out.push(child.clone());
+            self.collect_descendants(&child.id, visited, out);
+        }
+    }
+
+    /// Goal ids in dependency order - every goal appears before any goal
+    /// that relates to it (i.e. parents precede their children) - computed
+    /// via Kahn's algorithm over the `relations` edges. Errors if a cycle
+    /// makes a consistent ordering impossible.
+    pub fn topological_order(&self) -> Result<Vec<String>, String> {
+        let mut in_degree: HashMap<&str, usize> = self.goals.keys().map(|id| (id.as_str(), 0)).collect();
+        for relation in &self.relations {
+            if let Some(degree) = in_degree.get_mut(relation.from_id.as_str()) {
+                *degree += 1;
+            }
+        }
+
+        let mut ready: Vec<&str> = in_degree
+            .iter()
+            .filter(|&(_, &degree)| degree == 0)
+            .map(|(&id, _)| id)
+            .collect();
+        ready.sort();
+
+        let mut order = Vec::with_capacity(self.goals.len());
+        while let Some(id) = ready.pop() {
+            order.push(id.to_string());
+            let mut newly_ready = Vec::new();
+            for relation in self.relations.iter().filter(|r| r.to_id == id) {
+                if let Some(degree) = in_degree.get_mut(relation.from_id.as_str()) {
+                    *degree -= 1;
+                    if *degree == 0 {
+                        newly_ready.push(relation.from_id.as_str());
+                    }
+                }
+            }
+            newly_ready.sort();
+            ready.extend(newly_ready);
+            ready.sort();
+        }
+
+        if order.len() != self.goals.len() {
+            return Err("goal relations contain a cycle; no topological order exists".to_string());
+        }
+        Ok(order)
+    }
+
+    /// Every goal of type `Terminal`.
+    pub fn terminal_goals(&self) -> Vec<&Goal> {
+        self.goals.values().filter(|g| g.goal_type == GoalType::Terminal).collect()
+    }
+
+    /// All goals, ordered by descending priority, then by id for stability
+    /// among goals tied on priority.
+    pub fn goals_by_priority(&self) -> Vec<&Goal> {
+        let mut goals: Vec<&Goal> = self.goals.values().collect();
+        goals.sort_by(|a, b| b.priority.cmp(&a.priority).then_with(|| a.id.cmp(&b.id)));
+        goals
+    }
+
+    /// Set a goal's priority. Errors if the goal doesn't exist.
+    pub fn set_priority(&mut self, goal_id: &str, priority: u8) -> Result<(), String> {
+        let goal = self.goals.get_mut(goal_id).ok_or_else(|| format!("no goal with id '{goal_id}'"))?;
+        goal.priority = priority;
+        Ok(())
+    }
+
+    /// Goals with a deadline that has already passed as of `now`. There's
+    /// no completion status on `Goal` to exclude already-finished goals
+    /// with, so this is every goal whose `due_at` is in the past.
+    pub fn overdue_goals(&self, now: DateTime<Utc>) -> Vec<&Goal> {
+        self.goals.values().filter(|g| g.due_at.is_some_and(|due| due < now)).collect()
+    }
+
+    /// Render the goal graph as Graphviz DOT: each goal is a node shaped
+    /// by its `GoalType` (box for `Terminal`, ellipse for `Tactical`), and
+    /// each relation is an edge labeled with its relation type and
+    /// strength. Ids and labels are escaped so quotes/backslashes in goal
+    /// content can't break the DOT syntax.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph goal_graph {\n");
+        let mut ids: Vec<&String> = self.goals.keys().collect();
+        ids.sort();
+        for id in ids {
+            let goal = &self.goals[id];
+            let shape = match goal.goal_type {
+                GoalType::Terminal => "box",
+                GoalType::Tactical => "ellipse",
+            };
+            dot.push_str(&format!("    \"{}\" [shape={shape}];\n", escape_dot(id)));
+        }
+        for relation in &self.relations {
+            dot.push_str(&format!(
+                "    \"{}\" -> \"{}\" [label=\"{} ({:.2})\"];\n",
+                escape_dot(&relation.from_id),
+                escape_dot(&relation.to_id),
+                escape_dot(&relation.relation_type),
+                relation.strength
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Pairs of goal ids `(from_id, to_id)` connected by a relation that
+    /// actively undermines alignment: an explicit "contradicts" relation
+    /// type, or a relation with negative strength. Surfaces tactical goals
+    /// that work against each other.
+    pub fn detect_conflicts(&self) -> Vec<(String, String)> {
+        let mut conflicts: Vec<(String, String)> = self
+            .relations
+            .iter()
+            .filter(|r| r.relation_type == "contradicts" || r.strength < 0.0)
+            .map(|r| (r.from_id.clone(), r.to_id.clone()))
+            .collect();
+        conflicts.sort();
+        conflicts
+    }
+
+    /// Goals in direct conflict, as `(goal_a, goal_b, relation_type,
+    /// strength)` for every relation whose type is "blocks" or
+    /// "contradicts". Unlike `detect_conflicts`, which also flags negative-
+    /// strength relations as a heuristic, this only looks at explicit
+    /// conflict types, and keeps the type and strength so a planner can
+    /// weigh how serious each conflict is.
+    pub fn detect_goal_conflicts(&self) -> Vec<(String, String, String, f32)> {
+        let mut conflicts: Vec<(String, String, String, f32)> = self
+            .relations
+            .iter()
+            .filter(|r| r.relation_type == "blocks" || r.relation_type == "contradicts")
+            .map(|r| (r.from_id.clone(), r.to_id.clone(), r.relation_type.clone(), r.strength))
+            .collect();
+        conflicts.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+        conflicts
+    }
+
+    /// The goals that `id` directly relates to - its parents in the goal
+    /// hierarchy.
+    fn get_parents(&self, id: &str) -> Vec<&Goal> {
+        self.relations
+            .iter()
+            .filter(|r| r.from_id == id)
+            .filter_map(|r| self.goals.get(&r.to_id))
+            .collect()
+    }
+
+    /// Every `GoalType::Terminal` goal reachable by following parents
+    /// transitively from `goal_id`, deduplicated. Errors if `goal_id`
+    /// doesn't exist. Cycle-safe: a goal already visited is not expanded
+    /// again.
+    pub fn terminal_ancestors(&self, goal_id: &str) -> Result<Vec<String>, String> {
+        if !self.goals.contains_key(goal_id) {
+            return Err(format!("no goal with id '{goal_id}'"));
+        }
+
+        let mut visiting = HashSet::new();
+        let mut terminals = HashSet::new();
+        self.collect_terminal_ancestors(goal_id, &mut visiting, &mut terminals);
+
+        let mut result: Vec<String> = terminals.into_iter().collect();
+        result.sort();
+        Ok(result)
+    }
+
+    fn collect_terminal_ancestors(&self, id: &str, visiting: &mut HashSet<String>, out: &mut HashSet<String>) {
+        if !visiting.insert(id.to_string()) {
+            return;
+        }
+        for parent in self.get_parents(id) {
+            if parent.goal_type == GoalType::Terminal {
+                out.insert(parent.id.clone());
+            }
+            self.collect_terminal_ancestors(&parent.id, visiting, out);
+        }
+    }
+
+    /// Import goals (and optionally relations between them) from a YAML or
+    /// JSON file - the format is picked by `path`'s extension, defaulting
+    /// to JSON for anything other than `.yaml`/`.yml`. Goals are added in
+    /// file order; a malformed entry's index is reported so the caller can
+    /// fix the source file. Returns the number of goals imported.
+    pub fn import(&mut self, path: &Path) -> Result<usize, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read '{}': {e}", path.display()))?;
+
+        let is_yaml = matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("yaml") | Some("yml")
+        );
+        let import: GoalImportFile = if is_yaml {
+            serde_yaml::from_str(&contents)
+                .map_err(|e| format!("invalid YAML in '{}': {e}", path.display()))?
+        } else {
+            serde_json::from_str(&contents)
+                .map_err(|e| format!("invalid JSON in '{}': {e}", path.display()))?
+        };
+
+        let mut imported = 0;
+        for (index, goal) in import.goals.into_iter().enumerate() {
+            if goal.id.trim().is_empty() {
+                return Err(format!("goal at index {index} has an empty id"));
+            }
+            if self.goals.contains_key(&goal.id) {
+                return Err(format!("goal at index {index} has duplicate id '{}'", goal.id));
+            }
+            self.add_goal(goal).map_err(|e| format!("goal at index {index}: {e}"))?;
+            imported += 1;
+        }
+
+        for (index, relation) in import.relations.into_iter().enumerate() {
+            self.relate_goals_typed(
+                &relation.from_id,
+                &relation.to_id,
+                relation.strength,
+                &relation.relation_type,
+            )
+            .map_err(|e| format!("relation at index {index}: {e}"))?;
+        }
+
+        Ok(imported)
+    }
+}
+
+/// The shape of a goal-import file: a list of goals and, optionally, the
+/// relations between them.
+#[derive(Debug, Deserialize)]
+struct GoalImportFile {
+    goals: Vec<Goal>,
+    #[serde(default)]
+    relations: Vec<GoalRelation>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn import_loads_goals_and_relations_from_json() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("goals.json");
+        fs::write(
+            &path,
+            r#"{
+                "goals": [
+                    {"id": "g1", "description": "read one paper a day", "goal_type": "Tactical"},
+                    {"id": "g2", "description": "become a world-class neuroscientist", "goal_type": "Terminal"}
+                ],
+                "relations": [
+                    {"from_id": "g1", "to_id": "g2", "strength": 0.8}
+                ]
+            }"#,
+        )
+        .expect("writes fixture");
+
+        let mut tracker = GoalTracker::new();
+        let imported = tracker.import(&path).expect("import succeeds");
+
+        assert_eq!(imported, 2);
+        assert_eq!(tracker.goals.len(), 2);
+        assert_eq!(tracker.relations.len(), 1);
+        assert_eq!(tracker.relations[0].from_id, "g1");
+        assert_eq!(tracker.relations[0].to_id, "g2");
+    }
+
+    #[test]
+    fn import_reports_the_index_of_a_malformed_relation() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("goals.json");
+        fs::write(
+            &path,
+            r#"{
+                "goals": [
+                    {"id": "g1", "description": "read one paper a day", "goal_type": "Tactical"}
+                ],
+                "relations": [
+                    {"from_id": "g1", "to_id": "no-such-goal", "strength": 0.8}
+                ]
+            }"#,
+        )
+        .expect("writes fixture");
+
+        let mut tracker = GoalTracker::new();
+        let err = tracker.import(&path).expect_err("relation targets a missing goal");
+        assert!(err.contains("index 0"));
+    }
+
+    fn tracker_with_two_goals() -> GoalTracker {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(Goal {
+            id: "g1".to_string(),
+            description: "read one paper a day".to_string(),
+            goal_type: GoalType::Tactical,
+            tags: Vec::new(),
+            confidence: 1.0,
+            priority: 0,
+            due_at: None,
+        }).expect("valid confidence");
+        tracker.add_goal(Goal {
+            id: "g2".to_string(),
+            description: "become a world-class neuroscientist".to_string(),
+            goal_type: GoalType::Terminal,
+            tags: Vec::new(),
+            confidence: 1.0,
+            priority: 0,
+            due_at: None,
+        }).expect("valid confidence");
+        tracker
+    }
+
+    #[test]
+    fn rejects_out_of_range_strength() {
+        let mut tracker = tracker_with_two_goals();
+        let err = tracker
+            .relate_goals("g1", "g2", 1.5)
+            .expect_err("1.5 is out of range");
+        assert!(err.contains("1.5"));
+    }
+
+    #[test]
+    fn rejects_nan_strength() {
+        let mut tracker = tracker_with_two_goals();
+        assert!(tracker.relate_goals("g1", "g2", f32::NAN).is_err());
+    }
+
+    #[test]
+    fn detect_alignment_drift_flags_weak_support() {
+        let mut tracker = tracker_with_two_goals();
+        tracker.relate_goals("g1", "g2", 0.3).expect("valid");
+
+        let drifting = tracker.detect_alignment_drift(0.5);
+        assert_eq!(drifting, vec![("g1".to_string(), 0.3)]);
+    }
+
+    #[test]
+    fn detect_alignment_drift_weighs_undermines_negatively() {
+        let mut tracker = tracker_with_two_goals();
+        tracker
+            .relate_goals_typed("g1", "g2", 0.8, "undermines")
+            .expect("valid");
+
+        let drifting = tracker.detect_alignment_drift(0.5);
+        assert_eq!(drifting, vec![("g1".to_string(), -0.8)]);
+    }
+
+    #[test]
+    fn detect_alignment_drift_ignores_well_aligned_goals() {
+        let mut tracker = tracker_with_two_goals();
+        tracker.relate_goals("g1", "g2", 0.9).expect("valid");
+
+        assert!(tracker.detect_alignment_drift(0.5).is_empty());
+    }
+
+    #[test]
+    fn detect_alignment_drift_orders_by_ascending_strength_then_id() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(Goal {
+            id: "terminal".to_string(),
+            description: "become a world-class neuroscientist".to_string(),
+            goal_type: GoalType::Terminal,
+            tags: Vec::new(),
+            confidence: 1.0,
+            priority: 0,
+            due_at: None,
+        }).expect("valid confidence");
+        for (id, strength) in [("b", 0.4), ("a", 0.4), ("c", 0.1)] {
+            tracker.add_goal(Goal {
+                id: id.to_string(),
+                description: id.to_string(),
+                goal_type: GoalType::Tactical,
+                tags: Vec::new(),
+                confidence: 1.0,
+                priority: 0,
+                due_at: None,
+            }).expect("valid confidence");
+            tracker.relate_goals(id, "terminal", strength).expect("valid");
+        }
+
+        let drifting = tracker.detect_alignment_drift(0.5);
+        assert_eq!(
+            drifting,
+            vec![
+                ("c".to_string(), 0.1),
+                ("a".to_string(), 0.4),
+                ("b".to_string(), 0.4),
+            ]
+        );
+    }
+
+    #[test]
+    fn deepest_goal_chains_finds_the_longest_paths() {
+        let mut tracker = GoalTracker::new();
+        for id in ["g1", "g2", "g3", "g4"] {
+            tracker.add_goal(Goal {
+                id: id.to_string(),
+                description: id.to_string(),
+                goal_type: GoalType::Tactical,
+                tags: Vec::new(),
+                confidence: 1.0,
+                priority: 0,
+                due_at: None,
+            }).expect("valid confidence");
+        }
+        // g1 -> g2 -> g3 -> g4 (depth 4) and a shorter g1 -> g4 (depth 2).
+        tracker.relate_goals("g1", "g2", 0.9).unwrap();
+        tracker.relate_goals("g2", "g3", 0.9).unwrap();
+        tracker.relate_goals("g3", "g4", 0.9).unwrap();
+        tracker.relate_goals("g1", "g4", 0.9).unwrap();
+
+        let deepest = tracker.deepest_goal_chains();
+        assert_eq!(deepest.len(), 1);
+        assert_eq!(
+            deepest[0],
+            vec!["g1".to_string(), "g2".to_string(), "g3".to_string(), "g4".to_string()]
+        );
+    }
+
+    #[test]
+    fn transitive_strength_multiplies_along_a_chain() {
+        let mut tracker = GoalTracker::new();
+        for id in ["g1", "g2", "g3"] {
+            tracker.add_goal(Goal {
+                id: id.to_string(),
+                description: id.to_string(),
+                goal_type: GoalType::Tactical,
+                tags: Vec::new(),
+                confidence: 1.0,
+                priority: 0,
+                due_at: None,
+            }).expect("valid confidence");
+        }
+        tracker.relate_goals("g1", "g2", 0.8).expect("valid");
+        tracker.relate_goals("g2", "g3", 0.5).expect("valid");
+
+        let strength = tracker
+            .transitive_strength("g1", "g3")
+            .expect("g1 reaches g3 via g2");
+        assert!((strength - 0.4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn transitive_strength_is_none_when_unreachable() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(Goal {
+            id: "g1".to_string(),
+            description: "g1".to_string(),
+            goal_type: GoalType::Tactical,
+            tags: Vec::new(),
+            confidence: 1.0,
+            priority: 0,
+            due_at: None,
+        }).expect("valid confidence");
+        tracker.add_goal(Goal {
+            id: "g2".to_string(),
+            description: "g2".to_string(),
+            goal_type: GoalType::Terminal,
+            tags: Vec::new(),
+            confidence: 1.0,
+            priority: 0,
+            due_at: None,
+        }).expect("valid confidence");
+
+        assert_eq!(tracker.transitive_strength("g1", "g2"), None);
+    }
+
+    #[test]
+    fn event_log_records_operations_only_when_enabled() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(Goal {
+            id: "g1".to_string(),
+            description: "without logging".to_string(),
+            goal_type: GoalType::Tactical,
+            tags: Vec::new(),
+            confidence: 1.0,
+            priority: 0,
+            due_at: None,
+        }).expect("valid confidence");
+        assert!(tracker.events().is_empty());
+
+        tracker.set_event_logging(true);
+        tracker.add_goal(Goal {
+            id: "g2".to_string(),
+            description: "become a world-class neuroscientist".to_string(),
+            goal_type: GoalType::Terminal,
+            tags: Vec::new(),
+            confidence: 1.0,
+            priority: 0,
+            due_at: None,
+        }).expect("valid confidence");
+        tracker.relate_goals("g1", "g2", 0.8).expect("both goals exist");
+        tracker.remove_goal("g1");
+
+        let events = tracker.events();
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[0], GoalEvent::GoalAdded { .. }));
+        assert!(matches!(events[1], GoalEvent::RelationAdded { .. }));
+        assert!(matches!(events[2], GoalEvent::GoalRemoved { .. }));
+
+        tracker.clear_events();
+        assert!(tracker.events().is_empty());
+    }
+
+    #[test]
+    fn tags_can_be_added_filtered_and_removed() {
+        let mut tracker = tracker_with_two_goals();
+        tracker.add_tag("g1", "research").expect("g1 exists");
+        tracker.add_tag("g1", "research").expect("dedup is fine");
+        tracker.add_tag("g2", "safety").expect("g2 exists");
+
+        assert_eq!(tracker.goals["g1"].tags, vec!["research".to_string()]);
+
+        let research_goals = tracker.goals_with_tag("research");
+        assert_eq!(research_goals.len(), 1);
+        assert_eq!(research_goals[0].id, "g1");
+
+        tracker.remove_tag("g1", "research").expect("g1 exists");
+        assert!(tracker.goals["g1"].tags.is_empty());
+    }
+
+    #[test]
+    fn tags_round_trip_through_serialization() {
+        let mut tracker = tracker_with_two_goals();
+        tracker.add_tag("g1", "research").expect("g1 exists");
+
+        let json = serde_json::to_string(&tracker.goals["g1"]).expect("serializes");
+        let restored: Goal = serde_json::from_str(&json).expect("deserializes");
+        assert_eq!(restored.tags, vec!["research".to_string()]);
+    }
+
+    #[test]
+    fn accepts_boundary_strengths() {
+        let mut tracker = tracker_with_two_goals();
+        tracker.relate_goals("g1", "g2", 0.0).expect("0.0 is valid");
+        tracker.relate_goals("g1", "g2", 1.0).expect("1.0 is valid");
+        assert_eq!(tracker.relations.len(), 2);
+    }
+
+    fn tracker_with_family_tree() -> GoalTracker {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(Goal {
+            id: "terminal".to_string(),
+            description: "become a world-class neuroscientist".to_string(),
+            goal_type: GoalType::Terminal,
+            tags: Vec::new(),
+            confidence: 1.0,
+            priority: 0,
+            due_at: None,
+        }).expect("valid confidence");
+        for id in ["instrumental-1", "instrumental-2"] {
+            tracker.add_goal(Goal {
+                id: id.to_string(),
+                description: id.to_string(),
+                goal_type: GoalType::Tactical,
+                tags: Vec::new(),
+                confidence: 1.0,
+                priority: 0,
+                due_at: None,
+            }).expect("valid confidence");
+            tracker.relate_goals(id, "terminal", 0.8).expect("both exist");
+        }
+        for (grandchild, parent) in [
+            ("tactical-1a", "instrumental-1"),
+            ("tactical-1b", "instrumental-1"),
+            ("tactical-2a", "instrumental-2"),
+        ] {
+            tracker.add_goal(Goal {
+                id: grandchild.to_string(),
+                description: grandchild.to_string(),
+                goal_type: GoalType::Tactical,
+                tags: Vec::new(),
+                confidence: 1.0,
+                priority: 0,
+                due_at: None,
+            }).expect("valid confidence");
+            tracker.relate_goals(grandchild, parent, 0.8).expect("both exist");
+        }
+        tracker
+    }
+
+    #[test]
+    fn get_children_returns_direct_supporters_only() {
+        let tracker = tracker_with_family_tree();
+        let mut children: Vec<&str> = tracker.get_children("terminal").iter().map(|g| g.id.as_str()).collect();
+        children.sort();
+        assert_eq!(children, vec!["instrumental-1", "instrumental-2"]);
+    }
+
+    #[test]
+    fn get_descendants_recurses_through_grandchildren() {
+        let tracker = tracker_with_family_tree();
+        let mut descendants: Vec<String> = tracker.get_descendants("terminal").into_iter().map(|g| g.id).collect();
+        descendants.sort();
+        assert_eq!(
+            descendants,
+            vec![
+                "instrumental-1".to_string(),
+                "instrumental-2".to_string(),
+                "tactical-1a".to_string(),
+                "tactical-1b".to_string(),
+                "tactical-2a".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn get_descendants_survives_a_cycle() {
+        let mut tracker = tracker_with_family_tree();
+        // Introduce a cycle: terminal now also "supports" instrumental-1.
+        tracker.relate_goals("terminal", "instrumental-1", 0.5).expect("both exist");
+
+        let descendants = tracker.get_descendants("terminal");
+        assert!(descendants.len() < 100, "cycle should not cause infinite recursion");
+    }
+
+    #[test]
+    fn get_descendants_does_not_duplicate_a_diamond_shared_grandchild() {
+        let mut tracker = tracker_with_family_tree();
+        // tactical-1a is already a grandchild of instrumental-1; make it
+        // also support instrumental-2, so it's reachable from "terminal"
+        // through two different instrumental parents.
+        tracker.relate_goals("tactical-1a", "instrumental-2", 0.5).expect("both exist");
+
+        let descendants: Vec<String> = tracker.get_descendants("terminal").into_iter().map(|g| g.id).collect();
+        let occurrences = descendants.iter().filter(|id| *id == "tactical-1a").count();
+        assert_eq!(occurrences, 1, "tactical-1a should be reported once despite two parents");
+    }
+
+    #[test]
+    fn topological_order_places_parents_before_children() {
+        let tracker = tracker_with_family_tree();
+        let order = tracker.topological_order().expect("family tree is a DAG");
+
+        let position = |id: &str| order.iter().position(|g| g == id).unwrap();
+        assert!(position("terminal") < position("instrumental-1"));
+        assert!(position("terminal") < position("instrumental-2"));
+        assert!(position("instrumental-1") < position("tactical-1a"));
+        assert!(position("instrumental-1") < position("tactical-1b"));
+        assert!(position("instrumental-2") < position("tactical-2a"));
+        assert_eq!(order.len(), tracker.goals.len());
+    }
+
+    #[test]
+    fn topological_order_errors_on_a_cycle() {
+        let mut tracker = GoalTracker::new();
+        for id in ["g1", "g2"] {
+            tracker.add_goal(Goal {
+                id: id.to_string(),
+                description: id.to_string(),
+                goal_type: GoalType::Tactical,
+                tags: Vec::new(),
+                confidence: 1.0,
+                priority: 0,
+                due_at: None,
+            }).expect("valid confidence");
+        }
+        tracker.relate_goals("g1", "g2", 0.5).expect("both exist");
+        tracker.relate_goals("g2", "g1", 0.5).expect("both exist");
+
+        assert!(tracker.topological_order().is_err());
+    }
+
+    #[test]
+    fn terminal_goals_returns_only_terminal_type() {
+        let tracker = tracker_with_family_tree();
+        let mut ids: Vec<&str> = tracker.terminal_goals().iter().map(|g| g.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["terminal"]);
+    }
+
+    #[test]
+    fn detect_conflicts_flags_contradicting_relations() {
+        let mut tracker = tracker_with_two_goals();
+        tracker
+            .relate_goals_typed("g1", "g2", 0.5, "contradicts")
+            .expect("both exist");
+
+        assert_eq!(
+            tracker.detect_conflicts(),
+            vec![("g1".to_string(), "g2".to_string())]
+        );
+    }
+
+    #[test]
+    fn detect_conflicts_flags_negative_strength_relations() {
+        let mut tracker = tracker_with_two_goals();
+        tracker.relations.push(GoalRelation {
+            from_id: "g1".to_string(),
+            to_id: "g2".to_string(),
+            strength: -0.3,
+            relation_type: "supports".to_string(),
+        });
+
+        assert_eq!(
+            tracker.detect_conflicts(),
+            vec![("g1".to_string(), "g2".to_string())]
+        );
+    }
+
+    #[test]
+    fn detect_conflicts_ignores_well_aligned_relations() {
+        let mut tracker = tracker_with_two_goals();
+        tracker.relate_goals("g1", "g2", 0.8).expect("both exist");
+        assert!(tracker.detect_conflicts().is_empty());
+    }
+
+    #[test]
+    fn detect_goal_conflicts_reports_a_blocks_relation_with_its_type_and_strength() {
+        let mut tracker = tracker_with_two_goals();
+        tracker
+            .relate_goals_typed("g1", "g2", 0.6, "blocks")
+            .expect("both exist");
+
+        assert_eq!(
+            tracker.detect_goal_conflicts(),
+            vec![("g1".to_string(), "g2".to_string(), "blocks".to_string(), 0.6)]
+        );
+    }
+
+    #[test]
+    fn detect_goal_conflicts_ignores_supporting_relations() {
+        let mut tracker = tracker_with_two_goals();
+        tracker.relate_goals("g1", "g2", 0.8).expect("both exist");
+        assert!(tracker.detect_goal_conflicts().is_empty());
+    }
+
+    #[test]
+    fn terminal_ancestors_follows_multiple_branches() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(Goal {
+            id: "tactical".to_string(),
+            description: "read a paper".to_string(),
+            goal_type: GoalType::Tactical,
+            tags: Vec::new(),
+            confidence: 1.0,
+            priority: 0,
+            due_at: None,
+        }).expect("valid confidence");
+        for (terminal, via) in [("terminal-a", "branch-a"), ("terminal-b", "branch-b")] {
+            tracker.add_goal(Goal {
+                id: via.to_string(),
+                description: via.to_string(),
+                goal_type: GoalType::Tactical,
+                tags: Vec::new(),
+                confidence: 1.0,
+                priority: 0,
+                due_at: None,
+            }).expect("valid confidence");
+            tracker.add_goal(Goal {
+                id: terminal.to_string(),
+                description: terminal.to_string(),
+                goal_type: GoalType::Terminal,
+                tags: Vec::new(),
+                confidence: 1.0,
+                priority: 0,
+                due_at: None,
+            }).expect("valid confidence");
+            tracker.relate_goals(via, terminal, 0.8).expect("both exist");
+        }
+        tracker.relate_goals("tactical", "branch-a", 0.8).expect("both exist");
+        tracker.relate_goals("tactical", "branch-b", 0.8).expect("both exist");
+
+        let terminals = tracker.terminal_ancestors("tactical").expect("tactical exists");
+        assert_eq!(terminals, vec!["terminal-a".to_string(), "terminal-b".to_string()]);
+    }
+
+    #[test]
+    fn terminal_ancestors_errors_on_unknown_goal() {
+        let tracker = GoalTracker::new();
+        assert!(tracker.terminal_ancestors("does-not-exist").is_err());
+    }
+
+    #[test]
+    fn goal_builder_defaults_type_tags_and_confidence() {
+        let goal = Goal::builder()
+            .id("read-daily")
+            .description("read one paper a day")
+            .build()
+            .expect("id was provided");
+
+        assert_eq!(goal.id, "read-daily");
+        assert_eq!(goal.goal_type, GoalType::Tactical);
+        assert_eq!(goal.tags, Vec::<String>::new());
+        assert_eq!(goal.confidence, 0.5);
+    }
+
+    #[test]
+    fn goal_builder_honors_explicit_type_and_confidence() {
+        let goal = Goal::builder()
+            .id("become-neuroscientist")
+            .type_(GoalType::Terminal)
+            .confidence(1.0)
+            .build()
+            .expect("id was provided");
+
+        assert_eq!(goal.goal_type, GoalType::Terminal);
+        assert_eq!(goal.confidence, 1.0);
+    }
+
+    #[test]
+    fn goal_builder_requires_an_id() {
+        assert!(Goal::builder().description("no id").build().is_err());
+    }
+
+    #[test]
+    fn drift_report_includes_the_full_path_up_to_the_terminal() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(Goal::builder().id("read-daily").type_(GoalType::Tactical).build().unwrap()).expect("valid confidence");
+        tracker.add_goal(Goal::builder().id("publish-papers").type_(GoalType::Tactical).build().unwrap()).expect("valid confidence");
+        tracker
+            .add_goal(Goal::builder().id("become-neuroscientist").type_(GoalType::Terminal).build().unwrap()).expect("valid confidence");
+        tracker.relate_goals("read-daily", "publish-papers", 0.9).expect("both exist");
+        tracker.relate_goals("publish-papers", "become-neuroscientist", 0.3).expect("both exist");
+
+        let reports = tracker.drift_report();
+        let report = reports
+            .iter()
+            .find(|r| r.goal_id == "read-daily")
+            .expect("read-daily has a path to a terminal");
+
+        assert_eq!(
+            report.path,
+            vec!["read-daily".to_string(), "publish-papers".to_string(), "become-neuroscientist".to_string()]
+        );
+        assert_eq!(report.weakest_edge, ("publish-papers".to_string(), "become-neuroscientist".to_string(), 0.3));
+        assert!((report.alignment - 0.27).abs() < 1e-6);
+    }
+
+    #[test]
+    fn drift_report_omits_tactical_goals_with_no_path_to_a_terminal() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(Goal::builder().id("isolated").type_(GoalType::Tactical).build().unwrap()).expect("valid confidence");
+
+        assert!(tracker.drift_report().is_empty());
+    }
+
+    #[test]
+    fn relations_for_goal_finds_relations_on_either_end() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(Goal::builder().id("read-daily").type_(GoalType::Tactical).build().unwrap()).expect("valid confidence");
+        tracker.add_goal(Goal::builder().id("publish-papers").type_(GoalType::Tactical).build().unwrap()).expect("valid confidence");
+        tracker
+            .add_goal(Goal::builder().id("become-neuroscientist").type_(GoalType::Terminal).build().unwrap()).expect("valid confidence");
+        tracker.relate_goals("read-daily", "publish-papers", 0.9).expect("both exist");
+        tracker.relate_goals("publish-papers", "become-neuroscientist", 0.3).expect("both exist");
+
+        let relations = tracker.relations_for_goal("publish-papers");
+        assert_eq!(relations.len(), 2);
+        assert!(relations.iter().any(|r| r.from_id == "read-daily" && r.to_id == "publish-papers"));
+        assert!(relations.iter().any(|r| r.from_id == "publish-papers" && r.to_id == "become-neuroscientist"));
+    }
+
+    #[test]
+    fn relations_for_goal_is_empty_for_a_goal_with_no_relations() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(Goal::builder().id("isolated").type_(GoalType::Tactical).build().unwrap()).expect("valid confidence");
+        assert!(tracker.relations_for_goal("isolated").is_empty());
+    }
+
+    #[test]
+    fn goals_by_priority_sorts_descending_then_by_id() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(Goal::builder().id("b").priority(5).build().unwrap()).expect("valid confidence");
+        tracker.add_goal(Goal::builder().id("a").priority(5).build().unwrap()).expect("valid confidence");
+        tracker.add_goal(Goal::builder().id("c").priority(9).build().unwrap()).expect("valid confidence");
+        tracker.add_goal(Goal::builder().id("d").priority(0).build().unwrap()).expect("valid confidence");
+
+        let ids: Vec<&str> = tracker.goals_by_priority().iter().map(|g| g.id.as_str()).collect();
+        assert_eq!(ids, vec!["c", "a", "b", "d"]);
+    }
+
+    #[test]
+    fn set_priority_updates_an_existing_goal() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(Goal::builder().id("a").build().unwrap()).expect("valid confidence");
+        tracker.set_priority("a", 7).expect("a exists");
+        assert_eq!(tracker.goals["a"].priority, 7);
+    }
+
+    #[test]
+    fn set_priority_errors_for_an_unknown_goal() {
+        let mut tracker = GoalTracker::new();
+        assert!(tracker.set_priority("does-not-exist", 7).is_err());
+    }
+
+    #[test]
+    fn overdue_goals_reports_only_goals_past_their_deadline() {
+        let now = Utc::now();
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(Goal::builder().id("overdue").due_at(now - chrono::Duration::days(1)).build().unwrap()).expect("valid confidence");
+        tracker.add_goal(Goal::builder().id("future").due_at(now + chrono::Duration::days(1)).build().unwrap()).expect("valid confidence");
+        tracker.add_goal(Goal::builder().id("no-deadline").build().unwrap()).expect("valid confidence");
+
+        let overdue: Vec<&str> = tracker.overdue_goals(now).iter().map(|g| g.id.as_str()).collect();
+        assert_eq!(overdue, vec!["overdue"]);
+    }
+
+    #[test]
+    fn to_dot_shapes_terminal_goals_as_boxes_and_includes_edges() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(Goal::builder().id("read-daily").type_(GoalType::Tactical).build().unwrap()).expect("valid confidence");
+        tracker
+            .add_goal(Goal::builder().id("become-neuroscientist").type_(GoalType::Terminal).build().unwrap()).expect("valid confidence");
+        tracker.relate_goals("read-daily", "become-neuroscientist", 0.8).expect("both exist");
+
+        let dot = tracker.to_dot();
+        assert!(dot.starts_with("digraph"));
+        assert!(dot.contains("\"become-neuroscientist\" [shape=box]"));
+        assert!(dot.contains("\"read-daily\" [shape=ellipse]"));
+        assert!(dot.contains("\"read-daily\" -> \"become-neuroscientist\" [label=\"supports (0.80)\"]"));
+    }
+
+    #[test]
+    fn to_dot_escapes_quotes_in_ids() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(Goal::builder().id("say \"hi\"").build().unwrap()).expect("valid confidence");
+        let dot = tracker.to_dot();
+        assert!(dot.contains("\"say \\\"hi\\\"\""));
+    }
+
+    fn goal_with_confidence(confidence: f32) -> Goal {
+        Goal {
+            id: "g".to_string(),
+            description: "desc".to_string(),
+            goal_type: GoalType::Tactical,
+            tags: Vec::new(),
+            confidence,
+            priority: 0,
+            due_at: None,
+        }
+    }
+
+    #[test]
+    fn add_goal_rejects_out_of_range_confidence() {
+        let mut tracker = GoalTracker::new();
+        let err = tracker.add_goal(goal_with_confidence(1.5)).expect_err("1.5 is out of range");
+        assert!(err.contains("1.5"));
+        assert!(tracker.add_goal(goal_with_confidence(-0.1)).is_err());
+    }
+
+    #[test]
+    fn add_goal_rejects_nan_confidence() {
+        let mut tracker = GoalTracker::new();
+        assert!(tracker.add_goal(goal_with_confidence(f32::NAN)).is_err());
+    }
+
+    #[test]
+    fn add_goal_accepts_boundary_confidence() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(goal_with_confidence(0.0)).expect("0.0 is valid");
+        tracker.add_goal(goal_with_confidence(1.0)).expect("1.0 is valid");
+    }
+}
@@ -0,0 +1,128 @@
+//! Suggested repo path: src/http_server.rs
+//!
+//! A minimal, read-only HTTP front-end for a `MemoryGraph`, so other tools
+//! can query it without embedding this crate. Gated behind the `http`
+//! feature to keep it out of default builds and dependency trees.
+#![cfg(feature = "http")]
+
+use tiny_http::{Header, Method, Response, Server};
+
+use crate::memory_graph::MemoryGraph;
+
+/// Serve `graph` read-only over HTTP at `addr` until the process exits.
+///
+/// Endpoints:
+/// - `GET /concept/{key}` - the `SymbolicNode` as JSON, 404 if missing.
+/// - `GET /concepts` - a JSON array of all concept keys.
+/// - `GET /search?q=...` - concepts whose content contains `q`, 400 if `q`
+///   is missing.
+pub fn serve(graph: MemoryGraph, addr: &str) -> Result<(), String> {
+    let server = Server::http(addr).map_err(|e| format!("failed to bind {addr}: {e}"))?;
+    serve_bound(graph, server)
+}
+
+/// Like `serve`, but takes an already-bound `Server` instead of an address.
+/// Lets callers (notably tests that bind an ephemeral port) observe the
+/// real bound address before handing the listener off, instead of dropping
+/// and re-binding by address, which races with anything else on the
+/// machine that might grab the port in between.
+fn serve_bound(graph: MemoryGraph, server: Server) -> Result<(), String> {
+    for request in server.incoming_requests() {
+        let response = handle(&graph, request.method(), request.url());
+        let _ = request.respond(response);
+    }
+    Ok(())
+}
+
+fn json_header() -> Header {
+    Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap()
+}
+
+fn handle(graph: &MemoryGraph, method: &Method, url: &str) -> Response<std::io::Cursor<Vec<u8>>> {
+    if *method != Method::Get {
+        return Response::from_string("method not allowed").with_status_code(405);
+    }
+
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+
+    if path == "/concepts" {
+        let keys: Vec<&str> = graph.concepts.keys().map(|k| k.as_str()).collect();
+        let body = serde_json::to_string(&keys).unwrap_or_else(|_| "[]".to_string());
+        return Response::from_string(body).with_header(json_header());
+    }
+
+    if let Some(key) = path.strip_prefix("/concept/") {
+        return match graph.get_concept(key) {
+            Some(node) => {
+                let body = serde_json::to_string(node).unwrap_or_else(|_| "{}".to_string());
+                Response::from_string(body).with_header(json_header())
+            }
+            None => Response::from_string(format!("no concept with key '{key}'")).with_status_code(404),
+        };
+    }
+
+    if path == "/search" {
+        let query_param = query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("q="));
+        return match query_param {
+            None => Response::from_string("missing required query parameter 'q'").with_status_code(400),
+            Some(q) => {
+                let matches: Vec<&str> = graph
+                    .concepts
+                    .values()
+                    .filter(|n| n.content.contains(q))
+                    .map(|n| n.key.as_str())
+                    .collect();
+                let body = serde_json::to_string(&matches).unwrap_or_else(|_| "[]".to_string());
+                Response::from_string(body).with_header(json_header())
+            }
+        };
+    }
+
+    Response::from_string("not found").with_status_code(404)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory_graph::SymbolicNode;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use std::thread;
+
+    fn graph_with_kurisu() -> MemoryGraph {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(SymbolicNode {
+            key: "kurisu".to_string(),
+            content: "Child prodigy neuroscientist".to_string(),
+            confidence: 0.9,
+            framework: "biography".to_string(),
+            last_updated: Utc::now(),
+            provenance: None,
+            metadata: HashMap::new(),
+        });
+        graph
+    }
+
+    #[test]
+    fn serves_a_concept_over_http() {
+        let server = Server::http("127.0.0.1:0").expect("bind to an ephemeral port");
+        let actual_addr = server.server_addr().to_string();
+
+        // Serve directly from the listener we just bound, rather than
+        // dropping it and re-binding by address - that would leave a
+        // window for something else to grab the port first.
+        let graph = graph_with_kurisu();
+        thread::spawn(move || {
+            let _ = serve_bound(graph, server);
+        });
+
+        let body = ureq::get(&format!("http://{actual_addr}/concept/kurisu"))
+            .call()
+            .expect("request succeeds")
+            .into_string()
+            .expect("response is text");
+        assert!(body.contains("Child prodigy neuroscientist"));
+    }
+}
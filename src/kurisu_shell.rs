@@ -0,0 +1,1195 @@
+//! Suggested repo path: src/kurisu_shell.rs
+//!
+//! `KurisuShell` drives a single conversation session: it owns the
+//! dialogue history and the link to the `MemoryGraph` that
+//! backs Kurisu's long-term knowledge.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::goal_tracker::{Goal, GoalType};
+use crate::memory_graph::{MemoryGraph, SymbolicNode};
+use crate::proof_engine::ProofEngine;
+
+/// Below this confidence, a concept match in dialogue text isn't trusted
+/// enough to count as "identified".
+const DEFAULT_SYMBOL_CONFIDENCE_THRESHOLD: f32 = 0.5;
+
+/// Default `min_word_len` for `identify_symbols` calls that don't need a
+/// custom one, e.g. `compute_confidence`.
+const DEFAULT_MIN_WORD_LEN: usize = 4;
+
+/// Strip a common suffix (-ing, -ed, -s) from `word` so e.g. "machines"
+/// and "machine" stem to the same form, as long as `word` is at least
+/// `min_word_len` long - shorter words are left alone since stemming them
+/// tends to produce nonsense ("as" -> "a").
+fn stem(word: &str, min_word_len: usize) -> String {
+    if word.len() < min_word_len {
+        return word.to_string();
+    }
+    for suffix in ["ing", "ed", "s"] {
+        if let Some(stripped) = word.strip_suffix(suffix) {
+            if stripped.len() >= min_word_len {
+                return stripped.to_string();
+            }
+        }
+    }
+    word.to_string()
+}
+
+/// Split `text` into lowercased, stemmed words on non-alphanumeric
+/// boundaries, so matching is whole-word rather than substring.
+fn tokenize_and_stem(text: &str, min_word_len: usize) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| stem(&word.to_lowercase(), min_word_len))
+        .collect()
+}
+
+/// Turn free text into a lowercase, hyphen-separated id suitable for a
+/// `Goal::id`, e.g. "understand consciousness" -> "understand-consciousness".
+fn slugify(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Escape characters that would otherwise be interpreted as Markdown
+/// formatting (emphasis, headings, links, ...) so arbitrary dialogue
+/// content renders as plain text.
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '*' | '_' | '`' | '[' | ']' | '#' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DialogueEntry {
+    pub speaker: String,
+    pub content: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// A conversational style Kurisu can respond in, used by the default
+/// `ResponseGenerator` to pick a canned register.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PersonalityType {
+    #[default]
+    Analytical,
+    Philosophical,
+    Playful,
+}
+
+/// How confident Kurisu's response is, grounded in how well `input`
+/// matched known memory-graph concepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfidenceLevel {
+    Low,
+    Medium,
+    High,
+    Certain,
+}
+
+/// Everything a `ResponseGenerator` needs to produce a response: the
+/// dialogue so far and the personality to respond in. Borrows from the
+/// shell rather than copying its history.
+pub struct DialogueContext<'a> {
+    pub history: &'a [DialogueEntry],
+    pub personality: PersonalityType,
+}
+
+/// A pluggable strategy for turning user input into Kurisu's response,
+/// so callers can swap in an LLM or rule engine without forking
+/// `KurisuShell`.
+pub trait ResponseGenerator {
+    fn generate(&self, input: &str, ctx: &DialogueContext) -> String;
+}
+
+/// The built-in `ResponseGenerator`: a canned response per personality,
+/// preserving the shell's original hardcoded behavior.
+struct DefaultResponseGenerator;
+
+impl ResponseGenerator for DefaultResponseGenerator {
+    fn generate(&self, input: &str, ctx: &DialogueContext) -> String {
+        match ctx.personality {
+            PersonalityType::Analytical => format!("Let's examine that precisely: {input}"),
+            PersonalityType::Philosophical => format!("What does it mean that {input}?"),
+            PersonalityType::Playful => format!("Ehehe, {input}? Interesting~"),
+        }
+    }
+}
+
+/// A one-call overview of a session, returned by `KurisuShell::summarize`
+/// for logging at the end of a conversation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionSummary {
+    pub exchange_count: usize,
+    pub personality: PersonalityType,
+    /// Concepts identified in the session's dialogue, most-mentioned
+    /// first, ties broken by key for stable output.
+    pub top_concepts: Vec<(String, usize)>,
+    /// The first and last dialogue entry timestamps, as RFC 3339 strings,
+    /// or `None` if the session has no history yet.
+    pub span: Option<(String, String)>,
+}
+
+pub struct KurisuShell {
+    pub dialogue_history: Vec<DialogueEntry>,
+    /// Maximum total characters of history to include when building
+    /// context for a response. `None` means no limit.
+    context_budget: Option<usize>,
+    /// Minimum concept confidence for a symbol match in dialogue text to
+    /// be considered identified.
+    symbol_confidence_threshold: f32,
+    /// Concepts learned during this session, via `learn_concept`.
+    session_memory: MemoryGraph,
+    /// Keys of the concepts learned this session, so `export_learned` can
+    /// hand back just those without touching any pre-existing knowledge.
+    learned_keys: HashSet<String>,
+    /// A master graph this shell writes learned concepts through to, if
+    /// one was supplied via `with_shared_graph`. `None` for shells that
+    /// only track session-local learning.
+    shared_graph: Option<Arc<Mutex<MemoryGraph>>>,
+    personality: PersonalityType,
+    generator: Box<dyn ResponseGenerator>,
+    /// Maximum number of entries `dialogue_history` is allowed to grow to
+    /// before `push_entry` trims the oldest ones. `None` means unbounded.
+    max_history: Option<usize>,
+    /// Routes `prove:`-prefixed input to a `ProofEngine` instead of the
+    /// normal response generator, if one was supplied via
+    /// `with_proof_engine`.
+    proof_engine: Option<ProofEngine>,
+    /// An append-only JSONL log of every exchange, if one was opened via
+    /// `with_transcript`. `Mutex`-wrapped so `process_input` can write to
+    /// it while staying `&self`, like the rest of the shell's API.
+    transcript: Option<Mutex<fs::File>>,
+}
+
+impl KurisuShell {
+    pub fn new() -> Self {
+        Self {
+            dialogue_history: Vec::new(),
+            context_budget: None,
+            symbol_confidence_threshold: DEFAULT_SYMBOL_CONFIDENCE_THRESHOLD,
+            session_memory: MemoryGraph::new(),
+            learned_keys: HashSet::new(),
+            shared_graph: None,
+            personality: PersonalityType::default(),
+            generator: Box::new(DefaultResponseGenerator),
+            max_history: None,
+            proof_engine: None,
+            transcript: None,
+        }
+    }
+}
+
+impl Default for KurisuShell {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KurisuShell {
+    /// Build a shell seeded with `memory_graph` and `personality` that
+    /// appends every `process_input` exchange to `path` as JSONL - a user
+    /// entry followed by a system entry, flushed after each write so a
+    /// crash mid-session doesn't lose what's already happened. Errors if
+    /// `path` can't be opened for appending, rather than silently
+    /// dropping logs for the rest of the session.
+    pub fn with_transcript(memory_graph: MemoryGraph, personality: PersonalityType, path: &Path) -> Result<Self, String> {
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("failed to open transcript '{}': {e}", path.display()))?;
+        Ok(Self {
+            session_memory: memory_graph,
+            personality,
+            transcript: Some(Mutex::new(file)),
+            ..Self::new()
+        })
+    }
+
+    /// Append a user/system entry pair for this exchange to the
+    /// transcript file, if one is configured. Best-effort: a write
+    /// failure is silently dropped rather than panicking or bubbling up
+    /// through `process_input`'s existing signature.
+    fn log_to_transcript(&self, input: &str, response: &str) {
+        let Some(transcript) = &self.transcript else {
+            return;
+        };
+        let Ok(mut file) = transcript.lock() else {
+            return;
+        };
+        let now = Utc::now();
+        for entry in [
+            DialogueEntry {
+                speaker: "user".to_string(),
+                content: input.to_string(),
+                timestamp: now,
+            },
+            DialogueEntry {
+                speaker: "system".to_string(),
+                content: response.to_string(),
+                timestamp: now,
+            },
+        ] {
+            if let Ok(line) = serde_json::to_string(&entry) {
+                let _ = writeln!(file, "{line}");
+            }
+        }
+        let _ = file.flush();
+    }
+
+    /// Build a shell seeded with `memory_graph` and `personality` that
+    /// additionally routes `prove:`-prefixed input in `process_input` to
+    /// `engine` instead of the normal response generator.
+    pub fn with_proof_engine(memory_graph: MemoryGraph, personality: PersonalityType, engine: ProofEngine) -> Self {
+        Self {
+            session_memory: memory_graph,
+            personality,
+            proof_engine: Some(engine),
+            ..Self::new()
+        }
+    }
+
+    /// Build a shell seeded with an owned `graph` and `personality`,
+    /// without requiring the caller to wrap it in an `Arc` first - unlike
+    /// `with_shared_graph`, nothing else holds onto this graph, so there's
+    /// no need for shared ownership.
+    pub fn with_owned_graph(graph: MemoryGraph, personality: PersonalityType) -> Self {
+        Self {
+            session_memory: graph,
+            personality,
+            ..Self::new()
+        }
+    }
+
+    /// Build a shell that trims `dialogue_history` down to its last
+    /// `max_history` entries once it grows past that, so long sessions
+    /// don't grow unbounded. With an even `max_history` and a history that
+    /// grows a user/system pair per turn, whole pairs are kept; with an
+    /// odd `max_history` the oldest kept entry is half of a pair.
+    pub fn with_max_history(max_history: usize) -> Self {
+        Self {
+            max_history: Some(max_history),
+            ..Self::new()
+        }
+    }
+
+    /// Append `entry` to `dialogue_history` and trim the oldest entries if
+    /// `max_history` is set and exceeded. This is the preferred way to
+    /// grow the history in code that should respect `max_history`; tests
+    /// that intentionally build an unbounded history push to the public
+    /// field directly instead.
+    fn push_entry(&mut self, entry: DialogueEntry) {
+        self.dialogue_history.push(entry);
+        self.trim_history();
+    }
+
+    /// Drop the oldest entries until `dialogue_history` is within
+    /// `max_history`, then - if `max_history` is even - drop one more
+    /// should that leave an odd count, so a user/system pair at the front
+    /// isn't split awkwardly.
+    fn trim_history(&mut self) {
+        let Some(max_history) = self.max_history else {
+            return;
+        };
+        if self.dialogue_history.len() <= max_history {
+            return;
+        }
+        while self.dialogue_history.len() > max_history {
+            self.dialogue_history.remove(0);
+        }
+        if max_history.is_multiple_of(2) && !self.dialogue_history.len().is_multiple_of(2) {
+            self.dialogue_history.remove(0);
+        }
+    }
+
+    /// Build a shell that writes concepts learned via `learn_concept`
+    /// straight through to `graph`, so other holders of the same `Arc`
+    /// see them immediately instead of only after an explicit
+    /// `export_learned`/merge step.
+    pub fn with_shared_graph(graph: Arc<Mutex<MemoryGraph>>) -> Self {
+        Self {
+            shared_graph: Some(graph),
+            ..Self::new()
+        }
+    }
+
+    /// Build a shell that generates responses via `generator` instead of
+    /// the built-in canned-per-personality responses, so callers can plug
+    /// in an LLM or rule engine without forking `KurisuShell`.
+    pub fn with_generator(generator: Box<dyn ResponseGenerator>) -> Self {
+        Self {
+            generator,
+            ..Self::new()
+        }
+    }
+
+    /// Generate a response to `input` using the configured
+    /// `ResponseGenerator`, given the current dialogue history and
+    /// personality.
+    pub fn generate_response(&self, input: &str) -> String {
+        let ctx = DialogueContext {
+            history: &self.dialogue_history,
+            personality: self.personality,
+        };
+        self.generator.generate(input, &ctx)
+    }
+
+    /// Switch the personality used for future responses, recording a
+    /// system note in the dialogue history so the switch is visible when
+    /// reviewing or exporting the conversation.
+    pub fn set_personality(&mut self, personality: PersonalityType) {
+        self.personality = personality;
+        self.push_entry(DialogueEntry {
+            speaker: "system".to_string(),
+            content: format!("[personality switched to {personality:?}]"),
+            timestamp: Utc::now(),
+        });
+    }
+
+    /// How many memory-graph symbols `input` identifies, and how
+    /// confident we are overall: many high-confidence matches mean
+    /// `Certain`, some decent matches mean `High`, a weak or sparse match
+    /// means `Medium`, and no matches at all mean `Low`. Replaces the old
+    /// "Medium if it contains '?', else High" heuristic with something
+    /// grounded in what Kurisu actually knows.
+    fn compute_confidence(&self, input: &str, graph: &MemoryGraph) -> ConfidenceLevel {
+        let matched = self.identify_symbols(input, graph, DEFAULT_MIN_WORD_LEN);
+        if matched.is_empty() {
+            return ConfidenceLevel::Low;
+        }
+
+        let average_confidence: f32 = matched
+            .iter()
+            .filter_map(|key| graph.get_concept(key))
+            .map(|node| node.confidence)
+            .sum::<f32>()
+            / matched.len() as f32;
+
+        if matched.len() >= 2 && average_confidence >= 0.8 {
+            ConfidenceLevel::Certain
+        } else if average_confidence >= 0.6 {
+            ConfidenceLevel::High
+        } else {
+            ConfidenceLevel::Medium
+        }
+    }
+
+    /// Generate a response to `input` along with how confident that
+    /// response is, grounded in which `graph` symbols were identified in
+    /// the input.
+    pub fn process_input(&self, input: &str, graph: &MemoryGraph) -> (String, ConfidenceLevel) {
+        let (response, confidence) = if let Some(statement) = input.strip_prefix("prove:") {
+            (self.prove(statement.trim()), ConfidenceLevel::Certain)
+        } else {
+            (self.generate_response(input), self.compute_confidence(input, graph))
+        };
+        self.log_to_transcript(input, &response);
+        (response, confidence)
+    }
+
+    /// Hand `statement` to the configured `ProofEngine`, if any, and
+    /// describe the result in prose. Used by `process_input` for
+    /// `prove:`-prefixed input.
+    fn prove(&self, statement: &str) -> String {
+        match &self.proof_engine {
+            Some(engine) => match engine.verify_with_any_prover(statement) {
+                Ok(status) => format!("Proof status for '{statement}': {status}"),
+                Err(e) => format!("Couldn't attempt the proof: {e}"),
+            },
+            None => "Proving is unavailable: no proof engine is configured.".to_string(),
+        }
+    }
+
+    /// Detect a goal-expressing phrase in `input` ("I want to...", "my
+    /// goal is...", "I need to...") and, if found, propose a `Goal` the
+    /// caller can add to a `GoalTracker` themselves. This never mutates
+    /// anything - it's a suggestion, not a commitment. Returns `None` for
+    /// input that doesn't express a goal.
+    pub fn extract_goal_suggestion(&self, input: &str) -> Option<Goal> {
+        const GOAL_PHRASES: &[&str] = &["i want to ", "my goal is to ", "my goal is ", "i need to "];
+
+        let lower = input.to_lowercase();
+        let phrase = GOAL_PHRASES.iter().find(|phrase| lower.contains(*phrase))?;
+        let start = lower.find(*phrase)? + phrase.len();
+        let description = input[start..].trim().trim_end_matches('.').to_string();
+        if description.is_empty() {
+            return None;
+        }
+
+        Some(Goal {
+            id: slugify(&description),
+            description,
+            goal_type: GoalType::Terminal,
+            tags: Vec::new(),
+            confidence: 0.5,
+            priority: 0,
+            due_at: None,
+        })
+    }
+
+    /// Learn a concept for this session: always tracked locally (so
+    /// `export_learned` can hand it off to a master graph later), and
+    /// additionally written straight through to the shared graph if this
+    /// shell was built with one. A poisoned shared-graph lock (from a
+    /// panic in another holder) is recovered from rather than propagated,
+    /// since the graph's data is still usable.
+    pub fn learn_concept(&mut self, node: SymbolicNode) -> Result<(), String> {
+        self.learned_keys.insert(node.key.clone());
+        self.session_memory.add_concept(node.clone());
+
+        if let Some(shared) = &self.shared_graph {
+            let mut graph = shared.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            graph.add_concept(node);
+        }
+        Ok(())
+    }
+
+    /// A `MemoryGraph` containing only the concepts learned this session
+    /// (via `learn_concept`), for the caller to merge into a master graph
+    /// once they're ready to commit the session's learning.
+    pub fn export_learned(&self) -> MemoryGraph {
+        let mut exported = MemoryGraph::new();
+        for key in &self.learned_keys {
+            if let Some(node) = self.session_memory.get_concept(key) {
+                exported.add_concept(node.clone());
+            }
+        }
+        exported
+    }
+
+    /// Set the minimum concept confidence required for a symbol match in
+    /// dialogue text to count as identified. Must be within `[0.0, 1.0]`.
+    pub fn set_symbol_confidence_threshold(&mut self, threshold: f32) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(format!("threshold {threshold} must be within [0.0, 1.0]"));
+        }
+        self.symbol_confidence_threshold = threshold;
+        Ok(())
+    }
+
+    /// The concept keys from `graph` that appear in `text` with a
+    /// confidence at or above the configured threshold. Matching is by
+    /// whole word (after light stemming), not substring, so e.g. "art"
+    /// doesn't match "cartoon" in the text. `min_word_len` is the shortest
+    /// word that stemming is applied to, to avoid mangling short words
+    /// into nonsense.
+    pub fn identify_symbols<'a>(&self, text: &str, graph: &'a MemoryGraph, min_word_len: usize) -> Vec<&'a str> {
+        let text_words = tokenize_and_stem(text, min_word_len);
+        graph
+            .concepts
+            .values()
+            .filter(|node| node.confidence >= self.symbol_confidence_threshold)
+            .filter(|node| {
+                let key_words = tokenize_and_stem(&node.key, min_word_len);
+                !key_words.is_empty() && key_words.iter().all(|w| text_words.contains(w))
+            })
+            .map(|node| node.key.as_str())
+            .collect()
+    }
+
+    /// Cap the context passed to `generate_response_with_context` to at
+    /// most `max_chars` bytes of entry content (speaker labels and line
+    /// breaks are not counted), so a future model-backed implementation
+    /// doesn't blow its token budget on a long session. The latest entry
+    /// is always kept, even when it alone is longer than `max_chars`.
+    pub fn set_context_budget(&mut self, max_chars: usize) {
+        self.context_budget = Some(max_chars);
+    }
+
+    /// The entries that fit within the configured context budget: the most
+    /// recent entries, newest-first while accumulating, until adding the
+    /// next one would exceed the budget - always keeping at least the
+    /// latest entry even if it alone exceeds the budget.
+    fn context_window(&self) -> Vec<&DialogueEntry> {
+        let Some(budget) = self.context_budget else {
+            return self.dialogue_history.iter().collect();
+        };
+
+        let mut included = Vec::new();
+        let mut total_chars = 0;
+        for entry in self.dialogue_history.iter().rev() {
+            let entry_chars = entry.content.len();
+            if !included.is_empty() && total_chars + entry_chars > budget {
+                break;
+            }
+            included.push(entry);
+            total_chars += entry_chars;
+        }
+        included.reverse();
+        included
+    }
+
+    /// Build the context string that would be passed to a response
+    /// generator: the in-budget slice of history, rendered as
+    /// "Speaker: content" lines.
+    pub fn generate_response_with_context(&self) -> String {
+        self.context_window()
+            .iter()
+            .map(|entry| format!("{}: {}", entry.speaker, entry.content))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Find every dialogue entry whose content contains `query`
+    /// (case-insensitive), in chronological order, paired with its index
+    /// in `dialogue_history`.
+    pub fn search_history(&self, query: &str) -> Vec<(usize, &DialogueEntry)> {
+        let query = query.to_lowercase();
+        self.dialogue_history
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.content.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    /// A one-call overview of the session so far, suitable for logging when
+    /// a conversation ends. Concepts are tallied against `session_memory`
+    /// rather than any externally-supplied graph, since that's the only
+    /// graph a shell is guaranteed to have learned from by that point.
+    pub fn summarize(&self) -> SessionSummary {
+        let mut concept_counts: HashMap<String, usize> = HashMap::new();
+        for entry in &self.dialogue_history {
+            for key in self.identify_symbols(&entry.content, &self.session_memory, DEFAULT_MIN_WORD_LEN) {
+                *concept_counts.entry(key.to_string()).or_insert(0) += 1;
+            }
+        }
+        let mut top_concepts: Vec<(String, usize)> = concept_counts.into_iter().collect();
+        top_concepts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let span = match (self.dialogue_history.first(), self.dialogue_history.last()) {
+            (Some(first), Some(last)) => Some((first.timestamp.to_rfc3339(), last.timestamp.to_rfc3339())),
+            _ => None,
+        };
+
+        SessionSummary {
+            exchange_count: self.dialogue_history.len(),
+            personality: self.personality,
+            top_concepts,
+            span,
+        }
+    }
+
+    /// Clear the dialogue history. If `archive_to` is given, the current
+    /// history is first saved there as JSON, so it can be reloaded later.
+    pub fn clear_history(&mut self, archive_to: Option<&Path>) -> Result<(), String> {
+        if let Some(path) = archive_to {
+            let json = serde_json::to_string_pretty(&self.dialogue_history)
+                .map_err(|e| format!("failed to serialize dialogue history: {e}"))?;
+            fs::write(path, json).map_err(|e| format!("failed to write archive: {e}"))?;
+        }
+        self.dialogue_history.clear();
+        Ok(())
+    }
+
+    /// Render the dialogue history as Markdown: one `**speaker** (timestamp):
+    /// content` line per entry, with content escaped so it can't break the
+    /// surrounding formatting.
+    pub fn export_history_markdown(&self) -> String {
+        self.dialogue_history
+            .iter()
+            .map(|entry| {
+                format!(
+                    "**{}** ({}): {}",
+                    escape_markdown(&entry.speaker),
+                    entry.timestamp.to_rfc3339(),
+                    escape_markdown(&entry.content)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Render the dialogue history as a JSON array of `DialogueEntry`.
+    pub fn export_history_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(&self.dialogue_history)
+            .map_err(|e| format!("failed to serialize dialogue history: {e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn shell_with_history() -> KurisuShell {
+        let mut shell = KurisuShell::new();
+        shell.dialogue_history.push(DialogueEntry {
+            speaker: "Okabe".to_string(),
+            content: "Kurisu, status report.".to_string(),
+            timestamp: Utc::now(),
+        });
+        shell.dialogue_history.push(DialogueEntry {
+            speaker: "Kurisu".to_string(),
+            content: "All systems nominal.".to_string(),
+            timestamp: Utc::now(),
+        });
+        shell
+    }
+
+    #[test]
+    fn extract_goal_suggestion_detects_goal_expressing_phrases() {
+        let shell = KurisuShell::new();
+        let goal = shell
+            .extract_goal_suggestion("I want to understand consciousness")
+            .expect("input expresses a goal");
+        assert_eq!(goal.id, "understand-consciousness");
+        assert_eq!(goal.description, "understand consciousness");
+        assert_eq!(goal.goal_type, GoalType::Terminal);
+        assert_eq!(goal.confidence, 0.5);
+    }
+
+    #[test]
+    fn extract_goal_suggestion_returns_none_for_plain_input() {
+        let shell = KurisuShell::new();
+        assert!(shell.extract_goal_suggestion("All systems nominal.").is_none());
+    }
+
+    #[test]
+    fn clear_history_without_archive_empties_history() {
+        let mut shell = shell_with_history();
+        shell.clear_history(None).expect("clear succeeds");
+        assert!(shell.dialogue_history.is_empty());
+    }
+
+    #[test]
+    fn clear_history_with_archive_writes_a_loadable_file() {
+        let mut shell = shell_with_history();
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("history.json");
+
+        shell.clear_history(Some(&path)).expect("clear succeeds");
+        assert!(shell.dialogue_history.is_empty());
+
+        let contents = fs::read_to_string(&path).expect("archive file exists");
+        let restored: Vec<DialogueEntry> =
+            serde_json::from_str(&contents).expect("archive is valid JSON");
+        assert_eq!(restored.len(), 2);
+        assert_eq!(restored[0].content, "Kurisu, status report.");
+    }
+
+    #[test]
+    fn with_transcript_logs_each_exchange_as_a_jsonl_line_pair() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("transcript.jsonl");
+        let shell = KurisuShell::with_transcript(MemoryGraph::new(), PersonalityType::default(), &path)
+            .expect("transcript file opens");
+
+        let graph = MemoryGraph::new();
+        shell.process_input("hello there", &graph);
+        shell.process_input("how are you", &graph);
+
+        let contents = fs::read_to_string(&path).expect("transcript file exists");
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 4);
+        for line in &lines {
+            serde_json::from_str::<DialogueEntry>(line).expect("each line is a valid DialogueEntry");
+        }
+        assert_eq!(serde_json::from_str::<DialogueEntry>(lines[0]).unwrap().speaker, "user");
+        assert_eq!(serde_json::from_str::<DialogueEntry>(lines[1]).unwrap().speaker, "system");
+    }
+
+    #[test]
+    fn with_transcript_errors_when_the_path_cannot_be_opened() {
+        let path = Path::new("/nonexistent-directory/transcript.jsonl");
+        assert!(KurisuShell::with_transcript(MemoryGraph::new(), PersonalityType::default(), path).is_err());
+    }
+
+    #[test]
+    fn search_history_finds_matching_entries_case_insensitively() {
+        let shell = shell_with_history();
+        let matches = shell.search_history("STATUS");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].0, 0);
+        assert_eq!(matches[0].1.content, "Kurisu, status report.");
+    }
+
+    #[test]
+    fn search_history_then_clear_history_resets_between_scenarios() {
+        let mut shell = shell_with_history();
+        shell.push_entry(DialogueEntry {
+            speaker: "Okabe".to_string(),
+            content: "What's the status of the Time Leap Machine?".to_string(),
+            timestamp: Utc::now(),
+        });
+
+        let matches = shell.search_history("status");
+        assert_eq!(matches.len(), 2);
+
+        shell.clear_history(None).expect("clear succeeds");
+        assert!(shell.dialogue_history.is_empty());
+        assert!(shell.search_history("status").is_empty());
+    }
+
+    #[test]
+    fn summarize_reports_exchange_count_personality_concepts_and_span() {
+        use crate::memory_graph::SymbolicNode;
+
+        let mut shell = KurisuShell::new();
+        shell.set_personality(PersonalityType::Playful);
+        shell
+            .learn_concept(SymbolicNode {
+                key: "divergence meter".to_string(),
+                content: "measures the world-line's divergence number".to_string(),
+                confidence: 0.9,
+                framework: "sci-fi".to_string(),
+                last_updated: Utc::now(),
+                provenance: None,
+                metadata: HashMap::new(),
+            })
+            .expect("learn succeeds");
+        shell
+            .learn_concept(SymbolicNode {
+                key: "time leap machine".to_string(),
+                content: "sends memories back in time".to_string(),
+                confidence: 0.9,
+                framework: "sci-fi".to_string(),
+                last_updated: Utc::now(),
+                provenance: None,
+                metadata: HashMap::new(),
+            })
+            .expect("learn succeeds");
+
+        shell.push_entry(DialogueEntry {
+            speaker: "Okabe".to_string(),
+            content: "Check the Divergence Meter.".to_string(),
+            timestamp: Utc::now(),
+        });
+        shell.push_entry(DialogueEntry {
+            speaker: "Kurisu".to_string(),
+            content: "The Divergence Meter reads 1.048596.".to_string(),
+            timestamp: Utc::now(),
+        });
+        shell.push_entry(DialogueEntry {
+            speaker: "Okabe".to_string(),
+            content: "Did the Time Leap Machine send anything back?".to_string(),
+            timestamp: Utc::now(),
+        });
+
+        let summary = shell.summarize();
+
+        // 4, not 3: `set_personality` above recorded its own "[personality
+        // switched to ...]" system note into `dialogue_history` first.
+        assert_eq!(summary.exchange_count, 4);
+        assert_eq!(summary.personality, PersonalityType::Playful);
+        assert_eq!(
+            summary.top_concepts,
+            vec![("divergence meter".to_string(), 2), ("time leap machine".to_string(), 1)]
+        );
+        let (first, last) = summary.span.expect("history is non-empty");
+        assert_eq!(first, shell.dialogue_history[0].timestamp.to_rfc3339());
+        assert_eq!(last, shell.dialogue_history[3].timestamp.to_rfc3339());
+    }
+
+    #[test]
+    fn summarize_has_no_span_for_an_empty_session() {
+        let shell = KurisuShell::new();
+        let summary = shell.summarize();
+        assert_eq!(summary.exchange_count, 0);
+        assert!(summary.top_concepts.is_empty());
+        assert!(summary.span.is_none());
+    }
+
+    #[test]
+    fn identify_symbols_filters_by_confidence_threshold() {
+        use crate::memory_graph::SymbolicNode;
+
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(SymbolicNode {
+            key: "divergence meter".to_string(),
+            content: "measures the world-line's divergence number".to_string(),
+            confidence: 0.9,
+            framework: "sci-fi".to_string(),
+            last_updated: Utc::now(),
+            provenance: None,
+            metadata: HashMap::new(),
+        });
+        graph.add_concept(SymbolicNode {
+            key: "time leap machine".to_string(),
+            content: "sends memories back in time".to_string(),
+            confidence: 0.2,
+            framework: "sci-fi".to_string(),
+            last_updated: Utc::now(),
+            provenance: None,
+            metadata: HashMap::new(),
+        });
+
+        let shell = KurisuShell::new();
+        let symbols = shell.identify_symbols(
+            "Okabe checks the Divergence Meter and the Time Leap Machine",
+            &graph,
+            DEFAULT_MIN_WORD_LEN,
+        );
+        assert_eq!(symbols, vec!["divergence meter"]);
+    }
+
+    #[test]
+    fn identify_symbols_does_not_false_positive_on_substrings() {
+        use crate::memory_graph::SymbolicNode;
+
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(SymbolicNode {
+            key: "art".to_string(),
+            content: "the study of creative expression".to_string(),
+            confidence: 0.9,
+            framework: "general".to_string(),
+            last_updated: Utc::now(),
+            provenance: None,
+            metadata: HashMap::new(),
+        });
+
+        let shell = KurisuShell::new();
+        let symbols =
+            shell.identify_symbols("Mayuri loves drawing cartoon characters", &graph, DEFAULT_MIN_WORD_LEN);
+        assert!(symbols.is_empty(), "cartoon should not match concept key 'art'");
+    }
+
+    #[test]
+    fn identify_symbols_matches_plural_and_singular_forms() {
+        use crate::memory_graph::SymbolicNode;
+
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(SymbolicNode {
+            key: "time leap machine".to_string(),
+            content: "sends memories back in time".to_string(),
+            confidence: 0.9,
+            framework: "sci-fi".to_string(),
+            last_updated: Utc::now(),
+            provenance: None,
+            metadata: HashMap::new(),
+        });
+
+        let shell = KurisuShell::new();
+        let symbols = shell.identify_symbols(
+            "Okabe built several time leap machines in the lab",
+            &graph,
+            DEFAULT_MIN_WORD_LEN,
+        );
+        assert_eq!(symbols, vec!["time leap machine"]);
+    }
+
+    #[test]
+    fn set_symbol_confidence_threshold_rejects_out_of_range() {
+        let mut shell = KurisuShell::new();
+        assert!(shell.set_symbol_confidence_threshold(1.5).is_err());
+        assert!(shell.set_symbol_confidence_threshold(0.5).is_ok());
+    }
+
+    #[test]
+    fn context_budget_keeps_only_the_most_recent_entries() {
+        let mut shell = KurisuShell::new();
+        shell.dialogue_history.push(DialogueEntry {
+            speaker: "Okabe".to_string(),
+            content: "a".repeat(50),
+            timestamp: Utc::now(),
+        });
+        shell.dialogue_history.push(DialogueEntry {
+            speaker: "Kurisu".to_string(),
+            content: "b".repeat(50),
+            timestamp: Utc::now(),
+        });
+
+        shell.set_context_budget(60);
+        let context = shell.generate_response_with_context();
+        assert!(!context.contains(&"a".repeat(50)));
+        assert!(context.contains(&"b".repeat(50)));
+    }
+
+    #[test]
+    fn context_budget_always_keeps_at_least_the_latest_turn() {
+        let mut shell = KurisuShell::new();
+        shell.dialogue_history.push(DialogueEntry {
+            speaker: "Kurisu".to_string(),
+            content: "c".repeat(200),
+            timestamp: Utc::now(),
+        });
+        shell.set_context_budget(10);
+
+        let context = shell.generate_response_with_context();
+        assert!(context.contains(&"c".repeat(200)));
+    }
+
+    #[test]
+    fn search_history_returns_nothing_for_no_match() {
+        let shell = shell_with_history();
+        assert!(shell.search_history("divergence meter").is_empty());
+    }
+
+    #[test]
+    fn export_learned_contains_only_concepts_learned_this_session() {
+        let mut shell = KurisuShell::new();
+        shell
+            .learn_concept(SymbolicNode {
+                key: "divergence meter".to_string(),
+                content: "measures the world-line's divergence number".to_string(),
+                confidence: 0.9,
+                framework: "sci-fi".to_string(),
+                last_updated: Utc::now(),
+                provenance: None,
+                metadata: HashMap::new(),
+            })
+            .expect("learning succeeds");
+        shell
+            .learn_concept(SymbolicNode {
+                key: "time leap machine".to_string(),
+                content: "sends memories back in time".to_string(),
+                confidence: 0.7,
+                framework: "sci-fi".to_string(),
+                last_updated: Utc::now(),
+                provenance: None,
+                metadata: HashMap::new(),
+            })
+            .expect("learning succeeds");
+
+        let exported = shell.export_learned();
+        assert_eq!(exported.concepts.len(), 2);
+        assert!(exported.get_concept("divergence meter").is_some());
+        assert!(exported.get_concept("time leap machine").is_some());
+    }
+
+    #[test]
+    fn learn_concept_writes_through_to_the_shared_graph() {
+        let shared = Arc::new(Mutex::new(MemoryGraph::new()));
+        let mut shell = KurisuShell::with_shared_graph(shared.clone());
+
+        shell
+            .learn_concept(SymbolicNode {
+                key: "divergence meter".to_string(),
+                content: "measures the world-line's divergence number".to_string(),
+                confidence: 0.9,
+                framework: "sci-fi".to_string(),
+                last_updated: Utc::now(),
+                provenance: None,
+                metadata: HashMap::new(),
+            })
+            .expect("learning succeeds");
+
+        let graph = shared.lock().unwrap();
+        assert!(graph.get_concept("divergence meter").is_some());
+    }
+
+    #[test]
+    fn learn_concept_recovers_from_a_poisoned_shared_graph_lock() {
+        let shared = Arc::new(Mutex::new(MemoryGraph::new()));
+        let poisoned = shared.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoned.lock().unwrap();
+            panic!("deliberately poison the lock");
+        })
+        .join();
+
+        let mut shell = KurisuShell::with_shared_graph(shared.clone());
+        let result = shell.learn_concept(SymbolicNode {
+            key: "time leap machine".to_string(),
+            content: "sends memories back in time".to_string(),
+            confidence: 0.7,
+            framework: "sci-fi".to_string(),
+            last_updated: Utc::now(),
+            provenance: None,
+            metadata: HashMap::new(),
+        });
+
+        assert!(result.is_ok());
+    }
+
+    struct UppercaseEchoGenerator;
+
+    impl ResponseGenerator for UppercaseEchoGenerator {
+        fn generate(&self, input: &str, _ctx: &DialogueContext) -> String {
+            input.to_uppercase()
+        }
+    }
+
+    #[test]
+    fn custom_generator_is_used_instead_of_the_default() {
+        let shell = KurisuShell::with_generator(Box::new(UppercaseEchoGenerator));
+        assert_eq!(shell.generate_response("hello, kurisu"), "HELLO, KURISU");
+    }
+
+    #[test]
+    fn default_generator_varies_by_personality() {
+        let mut shell = KurisuShell::new();
+        let analytical = shell.generate_response("time travel is possible");
+        shell.personality = PersonalityType::Playful;
+        let playful = shell.generate_response("time travel is possible");
+        assert_ne!(analytical, playful);
+    }
+
+    #[test]
+    fn set_personality_switches_style_and_notes_it_in_history() {
+        let mut shell = KurisuShell::new();
+        let analytical = shell.generate_response("time travel is possible");
+
+        shell.set_personality(PersonalityType::Playful);
+        let playful = shell.generate_response("time travel is possible");
+
+        assert_ne!(analytical, playful);
+        assert_eq!(
+            shell.dialogue_history.last().expect("note was recorded").content,
+            "[personality switched to Playful]"
+        );
+    }
+
+    #[test]
+    fn with_max_history_trims_oldest_entries_keeping_pairs_intact() {
+        let mut shell = KurisuShell::with_max_history(4);
+        for i in 0..10 {
+            shell.push_entry(DialogueEntry {
+                speaker: if i % 2 == 0 { "Okabe" } else { "Kurisu" }.to_string(),
+                content: format!("turn {i}"),
+                timestamp: Utc::now(),
+            });
+        }
+
+        assert_eq!(shell.dialogue_history.len(), 4);
+        let contents: Vec<&str> = shell.dialogue_history.iter().map(|e| e.content.as_str()).collect();
+        assert_eq!(contents, vec!["turn 6", "turn 7", "turn 8", "turn 9"]);
+    }
+
+    #[test]
+    fn without_max_history_behavior_is_unchanged() {
+        let mut shell = KurisuShell::new();
+        for i in 0..10 {
+            shell.push_entry(DialogueEntry {
+                speaker: "Okabe".to_string(),
+                content: format!("turn {i}"),
+                timestamp: Utc::now(),
+            });
+        }
+        assert_eq!(shell.dialogue_history.len(), 10);
+    }
+
+    #[test]
+    fn process_input_is_certain_with_multiple_high_confidence_matches() {
+        use crate::memory_graph::SymbolicNode;
+
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(SymbolicNode {
+            key: "divergence meter".to_string(),
+            content: "measures the world-line's divergence number".to_string(),
+            confidence: 0.95,
+            framework: "sci-fi".to_string(),
+            last_updated: Utc::now(),
+            provenance: None,
+            metadata: HashMap::new(),
+        });
+        graph.add_concept(SymbolicNode {
+            key: "time leap machine".to_string(),
+            content: "sends memories back in time".to_string(),
+            confidence: 0.9,
+            framework: "sci-fi".to_string(),
+            last_updated: Utc::now(),
+            provenance: None,
+            metadata: HashMap::new(),
+        });
+
+        let shell = KurisuShell::new();
+        let (_, confidence) = shell.process_input(
+            "Okabe checks the Divergence Meter and the Time Leap Machine",
+            &graph,
+        );
+        assert_eq!(confidence, ConfidenceLevel::Certain);
+    }
+
+    #[test]
+    fn process_input_is_low_with_no_matches() {
+        let graph = MemoryGraph::new();
+        let shell = KurisuShell::new();
+        let (_, confidence) = shell.process_input("what time is it", &graph);
+        assert_eq!(confidence, ConfidenceLevel::Low);
+    }
+
+    #[test]
+    fn process_input_routes_prove_prefixed_input_to_the_proof_engine() {
+        use crate::proof_engine::{MockProver, ProofEngine, ProofStatus};
+
+        let mut engine = ProofEngine::new();
+        engine.add_prover(Box::new(MockProver::new("mock", ProofStatus::Proved)));
+
+        let shell = KurisuShell::with_proof_engine(MemoryGraph::new(), PersonalityType::Analytical, engine);
+        let graph = MemoryGraph::new();
+        let (response, confidence) = shell.process_input("prove: forall n, n + 0 = n", &graph);
+
+        assert!(response.contains("proven"), "response was: {response}");
+        assert_eq!(confidence, ConfidenceLevel::Certain);
+    }
+
+    #[test]
+    fn process_input_reports_proving_unavailable_without_an_engine() {
+        let shell = KurisuShell::new();
+        let graph = MemoryGraph::new();
+        let (response, _) = shell.process_input("prove: forall n, n + 0 = n", &graph);
+        assert!(response.contains("unavailable"), "response was: {response}");
+    }
+
+    #[test]
+    fn with_owned_graph_seeds_the_shell_without_requiring_an_arc() {
+        use crate::memory_graph::SymbolicNode;
+
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(SymbolicNode {
+            key: "divergence meter".to_string(),
+            content: "measures the world-line's divergence number".to_string(),
+            confidence: 0.9,
+            framework: "sci-fi".to_string(),
+            last_updated: Utc::now(),
+            provenance: None,
+            metadata: HashMap::new(),
+        });
+
+        let shell = KurisuShell::with_owned_graph(graph.clone(), PersonalityType::Philosophical);
+
+        let symbols = shell.identify_symbols("Okabe checks the Divergence Meter", &graph, DEFAULT_MIN_WORD_LEN);
+        assert_eq!(symbols, vec!["divergence meter"]);
+        assert_eq!(shell.summarize().personality, PersonalityType::Philosophical);
+    }
+
+    #[test]
+    fn export_history_markdown_renders_each_turn() {
+        let shell = shell_with_history();
+        let markdown = shell.export_history_markdown();
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("**Okabe**"));
+        assert!(lines[0].contains("Kurisu, status report."));
+        assert!(lines[1].starts_with("**Kurisu**"));
+    }
+
+    #[test]
+    fn export_history_markdown_escapes_formatting_characters() {
+        let mut shell = KurisuShell::new();
+        shell.dialogue_history.push(DialogueEntry {
+            speaker: "Okabe".to_string(),
+            content: "*El Psy Kongroo* #worldline_1%".to_string(),
+            timestamp: Utc::now(),
+        });
+
+        let markdown = shell.export_history_markdown();
+        assert!(markdown.contains("\\*El Psy Kongroo\\* \\#worldline\\_1%"));
+    }
+
+    #[test]
+    fn export_history_json_round_trips() {
+        let shell = shell_with_history();
+        let json = shell.export_history_json().expect("serializes");
+        let restored: Vec<DialogueEntry> = serde_json::from_str(&json).expect("valid JSON");
+        assert_eq!(restored.len(), 2);
+        assert_eq!(restored[0].content, "Kurisu, status report.");
+    }
+}
@@ -0,0 +1,13 @@
+//! Suggested repo path: src/lib.rs
+//!
+//! Core library for the Fractal Amadeus symbolic layer: the memory graph,
+//! goal tracker, proof engine, and the Kurisu REPL shell that ties them
+//! together.
+
+pub mod goal_tracker;
+#[cfg(feature = "http")]
+pub mod http_server;
+pub mod kurisu_shell;
+pub mod memory_graph;
+pub mod proof_engine;
+pub mod repl;
@@ -0,0 +1,230 @@
+//! Suggested repo path: src/main.rs
+//!
+//! A minimal line-oriented CLI over `MemoryGraph` and `Repl`: reads
+//! commands from stdin, one per line, and prints a result line to
+//! stdout for each. Intended for scripting and integration tests rather
+//! than interactive use - see `repl.rs` for the underlying command logic
+//! this just dispatches to.
+//!
+//! `--script <path>` reads the same command grammar from a file instead
+//! of stdin, for reproducible setups without piping `writeln!` into a
+//! child process. `--strict` turns the first error line into a nonzero
+//! exit code instead of just printing it and moving on.
+
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use fractal_amadeus::memory_graph::{MemoryGraph, SymbolicRelation};
+use fractal_amadeus::repl::{parse_add_line, Repl};
+
+fn display_help() -> String {
+    [
+        "commands:",
+        "  add <key> <framework> <confidence> <content>   - add or update a concept",
+        "  get <key>                                       - print a concept",
+        "  relate <from>|<to>|<relation_type>|<strength>    - relate two concepts (strength defaults to 0.5)",
+        "  save <path>                                      - write the graph to <path> as JSON",
+        "  load <path>                                      - replace the graph with the one saved at <path>",
+        "  list [framework]                                 - list concept keys, optionally filtered by framework",
+        "  delete <key>                                     - remove a concept and its relationships",
+        "  help                                             - show this message",
+        "  quit                                             - exit",
+    ]
+    .join("\n")
+}
+
+fn handle_add(repl: &mut Repl, args: &str) -> String {
+    match parse_add_line(args) {
+        Ok(node) => {
+            let key = node.key.clone();
+            repl.add(node);
+            format!("added concept '{key}'")
+        }
+        Err(e) => format!("error: {e}"),
+    }
+}
+
+fn handle_get(repl: &Repl, args: &str) -> String {
+    let key = args.trim();
+    if key.is_empty() {
+        return "error: get requires a key".to_string();
+    }
+    match repl.graph.get_concept(key) {
+        Some(node) => format!(
+            "{}: {} (confidence: {:.2}, framework: {})",
+            node.key, node.content, node.confidence, node.framework
+        ),
+        None => format!("error: no concept named '{key}'"),
+    }
+}
+
+/// Parse and apply a `relate` command's arguments: `<from>|<to>|
+/// <relation_type>|<strength>`, where `strength` is optional and
+/// defaults to `0.5`.
+fn handle_relate(repl: &mut Repl, args: &str) -> String {
+    let fields: Vec<&str> = args.split('|').map(|s| s.trim()).collect();
+    if fields.len() < 3 || fields[0].is_empty() || fields[1].is_empty() || fields[2].is_empty() {
+        return "error: relate requires <from>|<to>|<relation_type>|<strength>".to_string();
+    }
+    let from = fields[0];
+    let to = fields[1];
+    let relation_type = fields[2];
+
+    let strength = match fields.get(3).filter(|s| !s.is_empty()) {
+        Some(raw) => match raw.parse::<f32>() {
+            Ok(value) => value,
+            Err(_) => return format!("error: strength '{raw}' is not a number"),
+        },
+        None => 0.5,
+    };
+
+    match repl.relate(SymbolicRelation {
+        from: from.to_string(),
+        to: to.to_string(),
+        relation_type: relation_type.to_string(),
+        strength,
+        last_updated: chrono::Utc::now(),
+    }) {
+        Ok(()) => format!("related '{from}' to '{to}' ({relation_type})"),
+        Err(e) => format!("error: {e}"),
+    }
+}
+
+/// Write `repl`'s graph to `args` (a path), leaving it untouched on
+/// failure so a bad path doesn't lose the in-memory session.
+fn handle_save(repl: &Repl, args: &str) -> String {
+    let path = args.trim();
+    if path.is_empty() {
+        return "error: save requires a path".to_string();
+    }
+    match repl.graph.save(Path::new(path)) {
+        Ok(()) => format!("saved to '{path}'"),
+        Err(e) => format!("error: {e}"),
+    }
+}
+
+/// Replace `repl`'s graph with the one saved at `args` (a path).
+fn handle_load(repl: &mut Repl, args: &str) -> String {
+    let path = args.trim();
+    if path.is_empty() {
+        return "error: load requires a path".to_string();
+    }
+    match MemoryGraph::load(Path::new(path)) {
+        Ok(graph) => {
+            repl.graph = graph;
+            format!("loaded from '{path}'")
+        }
+        Err(e) => format!("error: {e}"),
+    }
+}
+
+/// List every concept key with its framework and confidence, sorted by
+/// key; `args` (trimmed), if non-empty, filters to that framework.
+fn handle_list(repl: &Repl, args: &str) -> String {
+    let framework = args.trim();
+    if framework.is_empty() {
+        let mut lines = Vec::new();
+        for key in repl.graph.concept_keys() {
+            let node = repl.graph.get_concept(&key).expect("key came from concept_keys");
+            lines.push(format!("{key} ({}, confidence: {:.2})", node.framework, node.confidence));
+        }
+        if lines.is_empty() {
+            "no concepts".to_string()
+        } else {
+            lines.join("\n")
+        }
+    } else {
+        let nodes = repl.graph.concepts_by_framework(framework);
+        if nodes.is_empty() {
+            format!("no concepts in framework '{framework}'")
+        } else {
+            nodes
+                .iter()
+                .map(|node| format!("{} ({}, confidence: {:.2})", node.key, node.framework, node.confidence))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    }
+}
+
+/// Run one session's worth of commands from `reader`, one per line, same
+/// grammar whether it's stdin or a `--script` file. Stops on `exit`/
+/// `quit`, or immediately after the first error line if `strict` is set.
+/// Returns whether every line succeeded.
+fn run_commands<R: BufRead>(reader: R, strict: bool) -> bool {
+    let mut repl = Repl::new(MemoryGraph::new());
+    let mut stdout = io::stdout();
+    let mut all_ok = true;
+
+    for line in reader.lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(_) => break,
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (command, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let output = match command {
+            "add" => handle_add(&mut repl, rest),
+            "get" => handle_get(&repl, rest),
+            "relate" => handle_relate(&mut repl, rest),
+            "save" => handle_save(&repl, rest),
+            "load" => handle_load(&mut repl, rest),
+            "list" => handle_list(&repl, rest),
+            "delete" => handle_delete(&mut repl, rest),
+            "help" => display_help(),
+            "quit" | "exit" => break,
+            other => format!("error: unknown command '{other}'"),
+        };
+        let _ = writeln!(stdout, "{output}");
+        let _ = stdout.flush();
+
+        if output.starts_with("error:") {
+            all_ok = false;
+            if strict {
+                break;
+            }
+        }
+    }
+
+    all_ok
+}
+
+/// Remove the concept named by `args` (trimmed), reporting whether it
+/// existed and how many relationships it took with it.
+fn handle_delete(repl: &mut Repl, args: &str) -> String {
+    let key = args.trim();
+    if key.is_empty() {
+        return "error: delete requires a key".to_string();
+    }
+    let (removed, relations_removed) = repl.remove(key);
+    match removed {
+        Some(_) => format!("deleted concept '{key}' ({relations_removed} relationship(s) removed)"),
+        None => format!("error: no concept named '{key}'"),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let strict = args.iter().any(|a| a == "--strict");
+    let script_path = args.iter().position(|a| a == "--script").and_then(|i| args.get(i + 1));
+
+    let all_ok = match script_path {
+        Some(path) => match fs::File::open(path) {
+            Ok(file) => run_commands(io::BufReader::new(file), strict),
+            Err(e) => {
+                eprintln!("error: failed to open script '{path}': {e}");
+                std::process::exit(1);
+            }
+        },
+        None => run_commands(io::stdin().lock(), strict),
+    };
+
+    if strict && !all_ok {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,1917 @@
+//! Suggested repo path: src/memory_graph.rs
+//!
+//! The symbolic memory graph: a set of `SymbolicNode` concepts connected by
+//! `SymbolicRelation` edges. This is the long-term knowledge store behind
+//! `KurisuShell` - separate from the raw dialogue history.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// How to weight concepts when drawing a random sample with
+/// `MemoryGraph::sample_concepts`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleWeight {
+    /// More confident concepts are more likely to be drawn.
+    ByConfidence,
+    /// Less confident concepts are more likely to be drawn - useful for
+    /// surfacing shaky knowledge that needs review.
+    InverseConfidence,
+    /// Every concept is equally likely, regardless of confidence.
+    Uniform,
+}
+
+/// A configurable set of confidence thresholds for bucketing concepts
+/// into low/medium/high/certain bands - e.g. for a knowledge-base health
+/// gauge. Each threshold is the minimum confidence required to land in
+/// that band or higher; anything below `medium` is `Low`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConfidenceBands {
+    pub medium: f32,
+    pub high: f32,
+    pub certain: f32,
+}
+
+impl Default for ConfidenceBands {
+    fn default() -> Self {
+        Self {
+            medium: 0.5,
+            high: 0.75,
+            certain: 0.9,
+        }
+    }
+}
+
+/// Summary statistics over every concept's confidence - `0.0` across the
+/// board for an empty graph, rather than dividing by zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConfidenceDistribution {
+    pub min: f32,
+    pub mean: f32,
+    pub median: f32,
+    pub max: f32,
+}
+
+/// A full summary of the graph's shape: concept and relationship counts, a
+/// framework breakdown, a relation-type breakdown, and the confidence
+/// distribution across all concepts. `frameworks()` is a lighter-weight
+/// alternative when only the framework breakdown is needed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphStats {
+    pub concept_count: usize,
+    pub relationship_count: usize,
+    pub frameworks: Vec<(String, usize)>,
+    pub relation_types: Vec<(String, usize)>,
+    pub confidence: ConfidenceDistribution,
+}
+
+impl ConfidenceBands {
+    fn classify(&self, confidence: f32) -> ConfidenceBand {
+        if confidence >= self.certain {
+            ConfidenceBand::Certain
+        } else if confidence >= self.high {
+            ConfidenceBand::High
+        } else if confidence >= self.medium {
+            ConfidenceBand::Medium
+        } else {
+            ConfidenceBand::Low
+        }
+    }
+}
+
+/// Which confidence band a concept falls into under a given
+/// `ConfidenceBands` configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConfidenceBand {
+    Low,
+    Medium,
+    High,
+    Certain,
+}
+
+/// A single piece of symbolic knowledge: some content, how confident we are
+/// in it, and which framework (theory, discipline, etc.) it belongs to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolicNode {
+    pub key: String,
+    pub content: String,
+    pub confidence: f32,
+    pub framework: String,
+    pub last_updated: DateTime<Utc>,
+    /// Where this concept came from (e.g. "dialogue:2026-08-08", a paper
+    /// citation, "manual entry"). `None` for concepts with no recorded
+    /// origin, including ones saved before this field existed.
+    #[serde(default)]
+    pub provenance: Option<String>,
+    /// Arbitrary string tags (e.g. a "source" citation) that don't
+    /// warrant their own struct field. `#[serde(default)]` so graphs
+    /// saved before this field existed still load. Use `set_metadata`/
+    /// `get_metadata` rather than reaching into this directly.
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+impl SymbolicNode {
+    /// Start building a `SymbolicNode` keyed by `key`, with `confidence`
+    /// defaulted to `0.5` (overridable via `.confidence(..)`).
+    /// `last_updated` is always the time `build` is called; the builder
+    /// has no setter for it.
+    /// Shorter-lived than spelling out the full struct literal at every
+    /// call site (the CLI and tests were doing that constantly).
+    pub fn builder(key: &str) -> SymbolicNodeBuilder {
+        SymbolicNodeBuilder {
+            key: key.to_string(),
+            content: String::new(),
+            confidence: 0.5,
+            framework: String::new(),
+            provenance: None,
+            metadata: HashMap::new(),
+        }
+    }
+}
+
+/// Builder for `SymbolicNode`; see `SymbolicNode::builder`.
+pub struct SymbolicNodeBuilder {
+    key: String,
+    content: String,
+    confidence: f32,
+    framework: String,
+    provenance: Option<String>,
+    metadata: HashMap<String, String>,
+}
+
+impl SymbolicNodeBuilder {
+    pub fn content(mut self, content: &str) -> Self {
+        self.content = content.to_string();
+        self
+    }
+
+    pub fn framework(mut self, framework: &str) -> Self {
+        self.framework = framework.to_string();
+        self
+    }
+
+    pub fn confidence(mut self, confidence: f32) -> Self {
+        self.confidence = confidence;
+        self
+    }
+
+    pub fn provenance(mut self, provenance: &str) -> Self {
+        self.provenance = Some(provenance.to_string());
+        self
+    }
+
+    pub fn metadata(mut self, tag: &str, value: &str) -> Self {
+        self.metadata.insert(tag.to_string(), value.to_string());
+        self
+    }
+
+    /// Finish building, validating `confidence` the same way the rest of
+    /// this file does (e.g. `update_confidence`): it must fall within
+    /// `[0.0, 1.0]`.
+    pub fn build(self) -> Result<SymbolicNode, String> {
+        if self.confidence.is_nan() || !(0.0..=1.0).contains(&self.confidence) {
+            return Err(format!("confidence {} must be within [0.0, 1.0]", self.confidence));
+        }
+        Ok(SymbolicNode {
+            key: self.key,
+            content: self.content,
+            confidence: self.confidence,
+            framework: self.framework,
+            last_updated: Utc::now(),
+            provenance: self.provenance,
+            metadata: self.metadata,
+        })
+    }
+}
+
+/// A directed, typed, weighted edge between two concepts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolicRelation {
+    pub from: String,
+    pub to: String,
+    pub relation_type: String,
+    pub strength: f32,
+    /// When this relation's strength was last affirmed. New field - files
+    /// saved before it existed default to "now" at load time, so they
+    /// decay from the point they're next touched rather than erroring out.
+    #[serde(default = "Utc::now")]
+    pub last_updated: DateTime<Utc>,
+}
+
+/// Which direction a concept's confidence has been moving, per
+/// `MemoryGraph::confidence_trend`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Rising,
+    Falling,
+    Stable,
+    /// Fewer than two recorded confidence updates - not enough history to
+    /// call a direction.
+    Unknown,
+}
+
+/// The symbolic memory graph itself: concepts keyed by their unique key,
+/// plus the relationships between them.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct MemoryGraph {
+    pub concepts: HashMap<String, SymbolicNode>,
+    pub relationships: Vec<SymbolicRelation>,
+    /// Whether `update_confidence` records each change into
+    /// `confidence_history`. Off by default so callers who don't need a
+    /// trend don't pay the bookkeeping cost.
+    #[serde(default)]
+    history_enabled: bool,
+    /// Per-concept `(when, confidence)` history, populated by
+    /// `update_confidence` while `history_enabled` is set.
+    #[serde(default)]
+    confidence_history: HashMap<String, Vec<(DateTime<Utc>, f32)>>,
+    /// Whether `save` runs `validate` first and refuses to write if it
+    /// finds problems. Off by default so existing callers that already
+    /// trust their graph aren't surprised by a new failure mode.
+    #[serde(default)]
+    validate_on_save: bool,
+}
+
+impl MemoryGraph {
+    /// Create an empty memory graph.
+    pub fn new() -> Self {
+        Self {
+            concepts: HashMap::new(),
+            relationships: Vec::new(),
+            history_enabled: false,
+            confidence_history: HashMap::new(),
+            validate_on_save: false,
+        }
+    }
+
+    /// Turn confidence-history tracking on or off for `update_confidence`.
+    pub fn set_history_enabled(&mut self, enabled: bool) {
+        self.history_enabled = enabled;
+    }
+
+    /// Whether `save` should run `validate` first and refuse to write if
+    /// it finds problems. Off by default for backward compatibility.
+    pub fn set_validate_on_save(&mut self, enabled: bool) {
+        self.validate_on_save = enabled;
+    }
+
+    /// Find structural problems: relations referencing a concept that no
+    /// longer exists ("dangling"), and confidences or relation strengths
+    /// outside `0.0..=1.0`. Returns one message per issue found, empty if
+    /// the graph is healthy.
+    pub fn validate(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+        for (key, node) in &self.concepts {
+            if !(0.0..=1.0).contains(&node.confidence) {
+                issues.push(format!("concept '{key}' has out-of-range confidence {}", node.confidence));
+            }
+        }
+        for relation in &self.relationships {
+            if !self.concepts.contains_key(&relation.from) {
+                issues.push(format!(
+                    "relation '{}' -> '{}' references missing concept '{}'",
+                    relation.from, relation.to, relation.from
+                ));
+            }
+            if !self.concepts.contains_key(&relation.to) {
+                issues.push(format!(
+                    "relation '{}' -> '{}' references missing concept '{}'",
+                    relation.from, relation.to, relation.to
+                ));
+            }
+            if !(0.0..=1.0).contains(&relation.strength) {
+                issues.push(format!(
+                    "relation '{}' -> '{}' has out-of-range strength {}",
+                    relation.from, relation.to, relation.strength
+                ));
+            }
+        }
+        issues
+    }
+
+    /// Serialize this graph to `path` as pretty JSON. If `validate_on_save`
+    /// is set, `validate` runs first and the write is refused - returning
+    /// the issues it found, joined together - rather than persisting a
+    /// corrupt graph.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if self.validate_on_save {
+            let issues = self.validate();
+            if !issues.is_empty() {
+                return Err(format!("refusing to save an invalid graph: {}", issues.join("; ")));
+            }
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| format!("failed to serialize graph: {e}"))?;
+        fs::write(path, json).map_err(|e| format!("failed to write graph: {e}"))
+    }
+
+    /// Load a graph previously written by `save`.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path).map_err(|e| format!("failed to read graph: {e}"))?;
+        serde_json::from_str(&contents).map_err(|e| format!("failed to parse graph: {e}"))
+    }
+
+    /// Update `key`'s confidence to `new_confidence` (must be within
+    /// `[0.0, 1.0]`), refreshing its `last_updated` timestamp. If history
+    /// tracking is enabled, the change is also appended to that concept's
+    /// confidence history for `confidence_trend` to analyze.
+    pub fn update_confidence(&mut self, key: &str, new_confidence: f32, now: DateTime<Utc>) -> Result<(), String> {
+        if new_confidence.is_nan() || !(0.0..=1.0).contains(&new_confidence) {
+            return Err(format!("confidence {new_confidence} must be within [0.0, 1.0]"));
+        }
+
+        {
+            let node = self
+                .concepts
+                .get_mut(key)
+                .ok_or_else(|| format!("no concept with key '{key}'"))?;
+            node.confidence = new_confidence;
+            node.last_updated = now;
+        }
+
+        if self.history_enabled {
+            self.confidence_history
+                .entry(key.to_string())
+                .or_default()
+                .push((now, new_confidence));
+        }
+        Ok(())
+    }
+
+    /// Whether `key`'s confidence has been rising, falling, or holding
+    /// steady across its recorded history, or `Unknown` if fewer than two
+    /// updates have been recorded. `None` if `key` isn't a known concept.
+    pub fn confidence_trend(&self, key: &str) -> Option<Trend> {
+        if !self.concepts.contains_key(key) {
+            return None;
+        }
+
+        let history = self.confidence_history.get(key).map(Vec::as_slice).unwrap_or(&[]);
+        if history.len() < 2 {
+            return Some(Trend::Unknown);
+        }
+
+        let first = history.first().expect("len >= 2").1;
+        let last = history.last().expect("len >= 2").1;
+        Some(if last > first {
+            Trend::Rising
+        } else if last < first {
+            Trend::Falling
+        } else {
+            Trend::Stable
+        })
+    }
+
+    /// Add (or overwrite) a concept.
+    pub fn add_concept(&mut self, node: SymbolicNode) {
+        self.concepts.insert(node.key.clone(), node);
+    }
+
+    /// Look up a concept by key.
+    pub fn get_concept(&self, key: &str) -> Option<&SymbolicNode> {
+        self.concepts.get(key)
+    }
+
+    /// Look up a metadata tag on a concept, e.g. `("kurisu", "source")`.
+    /// `None` if the concept doesn't exist or has no such tag.
+    pub fn get_metadata(&self, key: &str, tag: &str) -> Option<&str> {
+        self.concepts.get(key)?.metadata.get(tag).map(|s| s.as_str())
+    }
+
+    /// Set a metadata tag on a concept, overwriting any existing value
+    /// for that tag. Errors if the concept doesn't exist.
+    pub fn set_metadata(&mut self, key: &str, tag: &str, value: &str) -> Result<(), String> {
+        let node = self.concepts.get_mut(key).ok_or_else(|| format!("no concept with key '{key}'"))?;
+        node.metadata.insert(tag.to_string(), value.to_string());
+        Ok(())
+    }
+
+    /// Every concept with confidence strictly below `threshold`, sorted
+    /// by confidence ascending - the least-confident concepts first, for
+    /// a "review low-confidence concepts" workflow.
+    pub fn concepts_below_confidence(&self, threshold: f32) -> Vec<(&String, &SymbolicNode)> {
+        let mut matches: Vec<(&String, &SymbolicNode)> =
+            self.concepts.iter().filter(|(_, node)| node.confidence < threshold).collect();
+        matches.sort_by(|a, b| a.1.confidence.total_cmp(&b.1.confidence));
+        matches
+    }
+
+    /// Every concept with confidence strictly above `threshold`, sorted
+    /// by confidence descending - the most-confident concepts first.
+    pub fn concepts_above_confidence(&self, threshold: f32) -> Vec<(&String, &SymbolicNode)> {
+        let mut matches: Vec<(&String, &SymbolicNode)> =
+            self.concepts.iter().filter(|(_, node)| node.confidence > threshold).collect();
+        matches.sort_by(|a, b| b.1.confidence.total_cmp(&a.1.confidence));
+        matches
+    }
+
+    /// Every concept reachable from `start` within `max_depth` hops,
+    /// following relationships in either direction, with its distance
+    /// from `start` (which is included at distance 0). A visited set
+    /// keeps cycles from looping forever - this is the shared bounded
+    /// traversal primitive `find_path`-style and descendant queries can
+    /// build on. Empty if `start` isn't a known concept.
+    pub fn concepts_within(&self, start: &str, max_depth: usize) -> Vec<(String, usize)> {
+        if !self.concepts.contains_key(start) {
+            return Vec::new();
+        }
+
+        let mut visited: HashSet<String> = HashSet::new();
+        visited.insert(start.to_string());
+        let mut result = vec![(start.to_string(), 0)];
+        let mut queue: VecDeque<(String, usize)> = VecDeque::new();
+        queue.push_back((start.to_string(), 0));
+
+        while let Some((current, depth)) = queue.pop_front() {
+            if depth >= max_depth {
+                continue;
+            }
+            for relation in self.get_relationships_for_concept(&current) {
+                let neighbor = if relation.from == current { &relation.to } else { &relation.from };
+                if visited.insert(neighbor.clone()) {
+                    result.push((neighbor.clone(), depth + 1));
+                    queue.push_back((neighbor.clone(), depth + 1));
+                }
+            }
+        }
+
+        result
+    }
+
+    /// The `limit` most recently updated concepts, newest first - what
+    /// the node has been thinking about lately. `last_updated` is
+    /// already a parsed `DateTime<Utc>`, so there's no unparseable-
+    /// timestamp case to guard against here.
+    pub fn recently_updated(&self, limit: usize) -> Vec<(&String, &SymbolicNode)> {
+        let mut concepts: Vec<(&String, &SymbolicNode)> = self.concepts.iter().collect();
+        concepts.sort_by_key(|(_, node)| std::cmp::Reverse(node.last_updated));
+        concepts.truncate(limit);
+        concepts
+    }
+
+    /// Every pair of concept keys linked by a "contradicts" relationship,
+    /// in relationship order.
+    pub fn contradictions(&self) -> Vec<(String, String)> {
+        self.relationships
+            .iter()
+            .filter(|r| r.relation_type == "contradicts")
+            .map(|r| (r.from.clone(), r.to.clone()))
+            .collect()
+    }
+
+    /// Whether the graph is free of contradictions between two
+    /// high-confidence (> 0.7) concepts. A contradiction between
+    /// low-confidence concepts isn't alarming - the node hasn't settled
+    /// on either belief yet - but two things it's confident about can't
+    /// both be true.
+    pub fn is_consistent(&self) -> bool {
+        self.contradictions().iter().all(|(from, to)| {
+            let from_confident = self.get_concept(from).is_some_and(|n| n.confidence > 0.7);
+            let to_confident = self.get_concept(to).is_some_and(|n| n.confidence > 0.7);
+            !(from_confident && to_confident)
+        })
+    }
+
+    /// Remove a concept by key, returning it if it existed.
+    pub fn delete_concept(&mut self, key: &str) -> Option<SymbolicNode> {
+        self.concepts.remove(key)
+    }
+
+    /// Like `delete_concept`, but also strips every relationship that
+    /// names `key` as either endpoint, so it never leaves a dangling
+    /// relation behind. Returns the removed concept (if any) alongside
+    /// how many relationships were removed with it.
+    pub fn remove_concept(&mut self, key: &str) -> (Option<SymbolicNode>, usize) {
+        let removed = self.concepts.remove(key);
+        let before = self.relationships.len();
+        self.relationships.retain(|r| r.from != key && r.to != key);
+        (removed, before - self.relationships.len())
+    }
+
+    /// Like `remove_concept`, but refuses to delete `key` if it
+    /// participates in more than `max_relations` relationships (as either
+    /// endpoint), returning an error listing the dependent relations
+    /// instead. Protects against silently gutting a heavily-relied-on
+    /// concept.
+    pub fn delete_concept_guarded(&mut self, key: &str, max_relations: usize) -> Result<SymbolicNode, String> {
+        let dependents: Vec<&SymbolicRelation> = self
+            .relationships
+            .iter()
+            .filter(|r| r.from == key || r.to == key)
+            .collect();
+
+        if dependents.len() > max_relations {
+            let described: Vec<String> = dependents
+                .iter()
+                .map(|r| format!("{} -[{}]-> {}", r.from, r.relation_type, r.to))
+                .collect();
+            return Err(format!(
+                "'{key}' participates in {} relationships (max {max_relations}): {}",
+                dependents.len(),
+                described.join(", ")
+            ));
+        }
+
+        self.remove_concept(key)
+            .0
+            .ok_or_else(|| format!("no concept with key '{key}'"))
+    }
+
+    /// Record a relationship between two concepts.
+    /// Add `relation`, rejecting it instead of silently corrupting the
+    /// graph: both endpoints must already be concepts, `from` and `to`
+    /// must differ (a self-loop never makes sense for traversal), and
+    /// `strength` must be within `[0.0, 1.0]`. If a relation with the
+    /// same `(from, to, relation_type)` already exists, it's updated in
+    /// place - `relation`'s `strength` and `last_updated` win - rather
+    /// than pushing a duplicate that would inflate
+    /// `get_relationships_for_concept`.
+    pub fn add_relationship(&mut self, relation: SymbolicRelation) -> Result<(), String> {
+        if relation.from == relation.to {
+            return Err(format!("relationship '{}' -> '{}' is a self-loop, which is not allowed", relation.from, relation.to));
+        }
+        if !self.concepts.contains_key(&relation.from) {
+            return Err(format!("no concept with key '{}'", relation.from));
+        }
+        if !self.concepts.contains_key(&relation.to) {
+            return Err(format!("no concept with key '{}'", relation.to));
+        }
+        if relation.strength.is_nan() || !(0.0..=1.0).contains(&relation.strength) {
+            return Err(format!("strength {} must be within [0.0, 1.0]", relation.strength));
+        }
+
+        let existing = self
+            .relationships
+            .iter_mut()
+            .find(|r| r.from == relation.from && r.to == relation.to && r.relation_type == relation.relation_type);
+        match existing {
+            Some(existing) => {
+                existing.strength = relation.strength;
+                existing.last_updated = relation.last_updated;
+            }
+            None => self.relationships.push(relation),
+        }
+        Ok(())
+    }
+
+    /// Every relationship directed from `from` to `to` (there can be more
+    /// than one, of different types).
+    pub fn relationship_between(&self, from: &str, to: &str) -> Vec<&SymbolicRelation> {
+        self.relationships.iter().filter(|r| r.from == from && r.to == to).collect()
+    }
+
+    /// Whether `a` and `b` are connected by a relationship in either
+    /// direction.
+    pub fn are_related(&self, a: &str, b: &str) -> bool {
+        !self.relationship_between(a, b).is_empty() || !self.relationship_between(b, a).is_empty()
+    }
+
+    /// Take a point-in-time snapshot of the graph, to be restored later via
+    /// `restore`.
+    pub fn snapshot(&self) -> MemoryGraph {
+        self.clone()
+    }
+
+    /// Replace the graph's contents with a previously taken snapshot.
+    pub fn restore(&mut self, snapshot: MemoryGraph) {
+        *self = snapshot;
+    }
+
+    /// A human-readable digest of a framework's concepts, most confident
+    /// first, each line prefixed with its confidence - useful for a quick
+    /// "what do we actually believe here, and how strongly" summary.
+    pub fn confidence_weighted_content(&self, framework: &str) -> String {
+        let mut concepts: Vec<&SymbolicNode> =
+            self.concepts.values().filter(|n| n.framework == framework).collect();
+        concepts.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+
+        concepts
+            .iter()
+            .map(|n| format!("[{:.2}] {}", n.confidence, n.content))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// All concepts whose recorded provenance matches `source` exactly.
+    pub fn concepts_from_source(&self, source: &str) -> Vec<&SymbolicNode> {
+        self.concepts
+            .values()
+            .filter(|n| n.provenance.as_deref() == Some(source))
+            .collect()
+    }
+
+    /// A histogram of relationship strengths, bucketed into ten bands of
+    /// width 0.1 (`[0.0, 0.1)`, `[0.1, 0.2)`, ..., `[0.9, 1.0]`), returned
+    /// as `(band_start, count)` pairs ordered by band.
+    pub fn relation_confidence_histogram(&self) -> Vec<(f32, usize)> {
+        let mut bands = vec![0usize; 10];
+        for relation in &self.relationships {
+            let band = ((relation.strength * 10.0) as usize).min(9);
+            bands[band] += 1;
+        }
+        bands
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| (i as f32 * 0.1, count))
+            .collect()
+    }
+
+    /// Count concepts per confidence band under `bands`, as a quick
+    /// health gauge for whether the knowledge base is mostly speculative
+    /// or well-established. Bands with no concepts are simply absent from
+    /// the map rather than present with a count of zero.
+    pub fn confidence_band_counts(&self, bands: &ConfidenceBands) -> HashMap<ConfidenceBand, usize> {
+        let mut counts = HashMap::new();
+        for node in self.concepts.values() {
+            *counts.entry(bands.classify(node.confidence)).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// The concept with the highest confidence within `framework`, or
+    /// `None` if the framework has no concepts. A quick query, compared to
+    /// filtering and sorting the whole graph by hand.
+    pub fn most_confident_in_framework(&self, framework: &str) -> Option<&SymbolicNode> {
+        self.concepts
+            .values()
+            .filter(|n| n.framework == framework)
+            .max_by(|a, b| a.confidence.total_cmp(&b.confidence))
+    }
+
+    /// All relationships (in either direction) touching `key`.
+    pub fn get_relationships_for_concept(&self, key: &str) -> Vec<&SymbolicRelation> {
+        self.relationships
+            .iter()
+            .filter(|r| r.from == key || r.to == key)
+            .collect()
+    }
+
+    /// Bucket relationships under every key in `keys` they touch, in a
+    /// single pass over `relationships` - O(relations) rather than
+    /// O(keys * relations) from calling `get_relationships_for_concept` in
+    /// a loop. Useful when rendering a neighborhood for several concepts
+    /// at once.
+    pub fn relationships_for_concepts(&self, keys: &[&str]) -> HashMap<String, Vec<&SymbolicRelation>> {
+        let wanted: std::collections::HashSet<&str> = keys.iter().copied().collect();
+        let mut buckets: HashMap<String, Vec<&SymbolicRelation>> =
+            keys.iter().map(|k| (k.to_string(), Vec::new())).collect();
+
+        for relation in &self.relationships {
+            if wanted.contains(relation.from.as_str()) {
+                buckets.get_mut(&relation.from).unwrap().push(relation);
+            }
+            if wanted.contains(relation.to.as_str()) {
+                buckets.get_mut(&relation.to).unwrap().push(relation);
+            }
+        }
+        buckets
+    }
+
+    /// Every concept key, sorted alphabetically. The natural companion to
+    /// `get_concept` when the caller doesn't already know what's in the
+    /// graph - e.g. a `list` command with no filter.
+    pub fn concept_keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.concepts.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    /// Every concept belonging to `framework`, sorted by key. Like
+    /// `concept_keys` but filtered, for a `list <framework>` command.
+    pub fn concepts_by_framework(&self, framework: &str) -> Vec<&SymbolicNode> {
+        let mut nodes: Vec<&SymbolicNode> =
+            self.concepts.values().filter(|node| node.framework == framework).collect();
+        nodes.sort_by(|a, b| a.key.cmp(&b.key));
+        nodes
+    }
+
+    /// List every distinct framework with how many concepts belong to it,
+    /// sorted by count descending then name. A lighter-weight alternative
+    /// to a full stats summary, handy for a framework picker UI.
+    pub fn frameworks(&self) -> Vec<(String, usize)> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for node in self.concepts.values() {
+            *counts.entry(node.framework.as_str()).or_insert(0) += 1;
+        }
+
+        let mut frameworks: Vec<(String, usize)> =
+            counts.into_iter().map(|(name, count)| (name.to_string(), count)).collect();
+        frameworks.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        frameworks
+    }
+
+    /// List every distinct relationship type with how many relations have
+    /// it, sorted by count descending then name.
+    pub fn relation_types(&self) -> Vec<(String, usize)> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for relation in &self.relationships {
+            *counts.entry(relation.relation_type.as_str()).or_insert(0) += 1;
+        }
+
+        let mut relation_types: Vec<(String, usize)> =
+            counts.into_iter().map(|(name, count)| (name.to_string(), count)).collect();
+        relation_types.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        relation_types
+    }
+
+    /// Min/mean/median/max confidence across every concept. `0.0` for all
+    /// four on an empty graph, rather than dividing by zero.
+    pub fn confidence_distribution(&self) -> ConfidenceDistribution {
+        let mut confidences: Vec<f32> = self.concepts.values().map(|n| n.confidence).collect();
+        if confidences.is_empty() {
+            return ConfidenceDistribution {
+                min: 0.0,
+                mean: 0.0,
+                median: 0.0,
+                max: 0.0,
+            };
+        }
+
+        confidences.sort_by(|a, b| a.total_cmp(b));
+        let min = confidences[0];
+        let max = confidences[confidences.len() - 1];
+        let mean = confidences.iter().sum::<f32>() / confidences.len() as f32;
+        let mid = confidences.len() / 2;
+        let median = if confidences.len().is_multiple_of(2) {
+            (confidences[mid - 1] + confidences[mid]) / 2.0
+        } else {
+            confidences[mid]
+        };
+
+        ConfidenceDistribution { min, mean, median, max }
+    }
+
+    /// A full summary of the graph's shape - see `GraphStats`.
+    pub fn stats(&self) -> GraphStats {
+        GraphStats {
+            concept_count: self.concepts.len(),
+            relationship_count: self.relationships.len(),
+            frameworks: self.frameworks(),
+            relation_types: self.relation_types(),
+            confidence: self.confidence_distribution(),
+        }
+    }
+
+    /// Group concept keys into connected components, treating every
+    /// relationship as an undirected edge. Isolated concepts form their own
+    /// singleton component. Components are ordered by size descending, and
+    /// the keys within each component are sorted.
+    pub fn connected_components(&self) -> Vec<Vec<String>> {
+        let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+        for key in self.concepts.keys() {
+            adjacency.entry(key.as_str()).or_default();
+        }
+        for relation in &self.relationships {
+            adjacency.entry(relation.from.as_str()).or_default().push(relation.to.as_str());
+            adjacency.entry(relation.to.as_str()).or_default().push(relation.from.as_str());
+        }
+
+        let mut visited: std::collections::HashSet<&str> = std::collections::HashSet::new();
+        let mut components: Vec<Vec<String>> = Vec::new();
+
+        for &start in adjacency.keys() {
+            if visited.contains(start) {
+                continue;
+            }
+            let mut component = Vec::new();
+            let mut stack = vec![start];
+            while let Some(key) = stack.pop() {
+                if !visited.insert(key) {
+                    continue;
+                }
+                component.push(key.to_string());
+                for &neighbor in adjacency.get(key).into_iter().flatten() {
+                    if !visited.contains(neighbor) {
+                        stack.push(neighbor);
+                    }
+                }
+            }
+            component.sort();
+            components.push(component);
+        }
+
+        components.sort_by_key(|component| std::cmp::Reverse(component.len()));
+        components
+    }
+
+    /// Multiply the confidence of every concept in `framework` by `factor`,
+    /// clamping the result to `[0.0, 1.0]` and refreshing `last_updated`.
+    /// Returns how many concepts were affected. Useful for bulk recalibration
+    /// instead of editing each concept by hand.
+    pub fn rescale_framework_confidence(
+        &mut self,
+        framework: &str,
+        factor: f32,
+        now: DateTime<Utc>,
+    ) -> Result<usize, String> {
+        if factor < 0.0 {
+            return Err(format!("factor {factor} must be >= 0"));
+        }
+
+        let mut affected = 0;
+        for node in self.concepts.values_mut() {
+            if node.framework == framework {
+                node.confidence = (node.confidence * factor).clamp(0.0, 1.0);
+                node.last_updated = now;
+                affected += 1;
+            }
+        }
+        Ok(affected)
+    }
+
+    /// Apply exponential decay to every relationship's strength based on
+    /// how long it's been since it was last affirmed, mirroring how
+    /// concept confidence decays over time: `strength *= 0.5 ^ (age_days /
+    /// half_life_days)`.
+    pub fn decay_relationships(&mut self, half_life_days: f64, now: DateTime<Utc>) {
+        for relation in &mut self.relationships {
+            let age_days = (now - relation.last_updated).num_seconds() as f64 / 86_400.0;
+            let decay_factor = 0.5_f64.powf(age_days.max(0.0) / half_life_days);
+            relation.strength = (relation.strength as f64 * decay_factor) as f32;
+        }
+    }
+
+    /// Render the graph as Graphviz DOT: one node per concept, one edge per
+    /// relationship, labelled with the relation type.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph memory_graph {\n");
+        for key in self.concepts.keys() {
+            dot.push_str(&format!("    \"{key}\";\n"));
+        }
+        for relation in &self.relationships {
+            dot.push_str(&format!(
+                "    \"{}\" -> \"{}\" [label=\"{}\"];\n",
+                relation.from, relation.to, relation.relation_type
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Refresh a concept's `last_updated` timestamp without touching its
+    /// content, confidence, or framework. Useful for re-affirming a belief
+    /// without actually editing it (relevant for decay-based forgetting).
+    pub fn touch(&mut self, key: &str, now: DateTime<Utc>) -> Result<(), String> {
+        let node = self
+            .concepts
+            .get_mut(key)
+            .ok_or_else(|| format!("no concept with key '{key}'"))?;
+        node.last_updated = now;
+        Ok(())
+    }
+
+    /// Draw up to `n` distinct concept keys at random, weighted according
+    /// to `weight`, using a seeded RNG so the draw is reproducible. Fewer
+    /// than `n` keys are returned if the graph has fewer concepts than
+    /// that.
+    pub fn sample_concepts(&self, n: usize, weight: SampleWeight, seed: u64) -> Vec<String> {
+        let mut keys: Vec<&String> = self.concepts.keys().collect();
+        keys.sort();
+
+        let mut remaining_weights: Vec<f64> = keys
+            .iter()
+            .map(|key| {
+                let confidence = self.concepts[*key].confidence as f64;
+                match weight {
+                    SampleWeight::ByConfidence => confidence.max(1e-6),
+                    SampleWeight::InverseConfidence => (1.0 - confidence).max(1e-6),
+                    SampleWeight::Uniform => 1.0,
+                }
+            })
+            .collect();
+        let mut remaining_keys: Vec<String> = keys.into_iter().cloned().collect();
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut sampled = Vec::with_capacity(n.min(remaining_keys.len()));
+        for _ in 0..n.min(remaining_keys.len()) {
+            let total: f64 = remaining_weights.iter().sum();
+            let mut draw = rng.gen::<f64>() * total;
+
+            let mut chosen = remaining_weights.len() - 1;
+            for (i, w) in remaining_weights.iter().enumerate() {
+                if draw < *w {
+                    chosen = i;
+                    break;
+                }
+                draw -= w;
+            }
+
+            sampled.push(remaining_keys.remove(chosen));
+            remaining_weights.remove(chosen);
+        }
+        sampled
+    }
+
+    /// How alike two concepts' `content` is, as the Jaccard index of their
+    /// lowercased word sets - `0.0` for no shared words, `1.0` for
+    /// identical word sets, regardless of word order or repetition.
+    /// `None` if either key is unknown.
+    pub fn concept_similarity(&self, a: &str, b: &str) -> Option<f32> {
+        let content_a = &self.concepts.get(a)?.content;
+        let content_b = &self.concepts.get(b)?.content;
+
+        let words_a: HashSet<String> = content_a.to_lowercase().split_whitespace().map(String::from).collect();
+        let words_b: HashSet<String> = content_b.to_lowercase().split_whitespace().map(String::from).collect();
+        if words_a.is_empty() && words_b.is_empty() {
+            return Some(1.0);
+        }
+
+        let intersection = words_a.intersection(&words_b).count();
+        let union = words_a.union(&words_b).count();
+        Some(intersection as f32 / union as f32)
+    }
+
+    /// Candidate concept pairs to merge: every distinct pair whose
+    /// `concept_similarity` exceeds `threshold`, as `(a, b, similarity)`
+    /// with `a < b` so a pair is never reported in both orders, sorted by
+    /// similarity descending for easy review.
+    pub fn suggest_merges(&self, threshold: f32) -> Vec<(String, String, f32)> {
+        let mut keys: Vec<&String> = self.concepts.keys().collect();
+        keys.sort();
+
+        let mut suggestions = Vec::new();
+        for (i, a) in keys.iter().enumerate() {
+            for b in &keys[i + 1..] {
+                let similarity = self
+                    .concept_similarity(a, b)
+                    .expect("both keys come from self.concepts");
+                if similarity > threshold {
+                    suggestions.push(((*a).clone(), (*b).clone(), similarity));
+                }
+            }
+        }
+        suggestions.sort_by(|x, y| y.2.total_cmp(&x.2));
+        suggestions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn node(key: &str, updated: DateTime<Utc>) -> SymbolicNode {
+        SymbolicNode {
+            key: key.to_string(),
+            content: "consciousness requires integrated information".to_string(),
+            confidence: 0.7,
+            framework: "IIT".to_string(),
+            last_updated: updated,
+            provenance: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn confidence_trend_is_rising_when_recorded_updates_end_higher() {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(node("iit-axiom-1", Utc::now()));
+        graph.set_history_enabled(true);
+
+        graph
+            .update_confidence("iit-axiom-1", 0.5, Utc::now())
+            .expect("valid confidence");
+        graph
+            .update_confidence("iit-axiom-1", 0.8, Utc::now())
+            .expect("valid confidence");
+
+        assert_eq!(graph.confidence_trend("iit-axiom-1"), Some(Trend::Rising));
+    }
+
+    #[test]
+    fn confidence_trend_is_unknown_with_fewer_than_two_updates() {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(node("iit-axiom-1", Utc::now()));
+        graph.set_history_enabled(true);
+
+        assert_eq!(graph.confidence_trend("iit-axiom-1"), Some(Trend::Unknown));
+        assert_eq!(graph.confidence_trend("no-such-concept"), None);
+    }
+
+    #[test]
+    fn confidence_band_counts_classifies_a_known_distribution() {
+        let mut graph = MemoryGraph::new();
+        for (key, confidence) in [
+            ("speculative-1", 0.1),
+            ("speculative-2", 0.3),
+            ("decent-1", 0.6),
+            ("solid-1", 0.8),
+            ("bedrock-1", 0.95),
+        ] {
+            let mut concept = node(key, Utc::now());
+            concept.confidence = confidence;
+            graph.add_concept(concept);
+        }
+
+        let counts = graph.confidence_band_counts(&ConfidenceBands::default());
+        assert_eq!(counts.get(&ConfidenceBand::Low), Some(&2));
+        assert_eq!(counts.get(&ConfidenceBand::Medium), Some(&1));
+        assert_eq!(counts.get(&ConfidenceBand::High), Some(&1));
+        assert_eq!(counts.get(&ConfidenceBand::Certain), Some(&1));
+    }
+
+    #[test]
+    fn touch_updates_only_the_timestamp() {
+        let original_time = Utc::now() - Duration::days(10);
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(node("iit-axiom-1", original_time));
+
+        let now = Utc::now();
+        graph.touch("iit-axiom-1", now).expect("concept exists");
+
+        let updated = graph.get_concept("iit-axiom-1").unwrap();
+        assert_eq!(updated.last_updated, now);
+        assert_eq!(updated.content, "consciousness requires integrated information");
+        assert_eq!(updated.confidence, 0.7);
+        assert_eq!(updated.framework, "IIT");
+    }
+
+    #[test]
+    fn touch_missing_key_errors() {
+        let mut graph = MemoryGraph::new();
+        assert!(graph.touch("does-not-exist", Utc::now()).is_err());
+    }
+
+    #[test]
+    fn to_dot_includes_concepts_and_relations() {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(node("kurisu", Utc::now()));
+        graph.add_concept(node("okabe", Utc::now()));
+        graph.relationships.push(SymbolicRelation {
+            from: "okabe".to_string(),
+            to: "kurisu".to_string(),
+            relation_type: "trusts".to_string(),
+            strength: 0.9,
+            last_updated: Utc::now(),
+        });
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph"));
+        assert!(dot.contains("\"kurisu\""));
+        assert!(dot.contains("\"okabe\" -> \"kurisu\""));
+    }
+
+    #[test]
+    fn rescale_framework_confidence_halves_matching_concepts() {
+        let mut graph = MemoryGraph::new();
+        let mut iit_1 = node("iit-1", Utc::now());
+        iit_1.confidence = 0.8;
+        let mut iit_2 = node("iit-2", Utc::now());
+        iit_2.confidence = 0.6;
+        iit_2.framework = "IIT".to_string();
+        let mut other = node("other", Utc::now());
+        other.framework = "GWT".to_string();
+        other.confidence = 0.8;
+
+        graph.add_concept(iit_1);
+        graph.add_concept(iit_2);
+        graph.add_concept(other);
+
+        let affected = graph
+            .rescale_framework_confidence("IIT", 0.5, Utc::now())
+            .expect("valid factor");
+
+        assert_eq!(affected, 2);
+        assert_eq!(graph.get_concept("iit-1").unwrap().confidence, 0.4);
+        assert_eq!(graph.get_concept("iit-2").unwrap().confidence, 0.3);
+        assert_eq!(graph.get_concept("other").unwrap().confidence, 0.8);
+    }
+
+    #[test]
+    fn confidence_weighted_content_orders_most_confident_first() {
+        let mut graph = MemoryGraph::new();
+        let mut low = node("iit-1", Utc::now());
+        low.confidence = 0.3;
+        low.content = "weakly held claim".to_string();
+        let mut high = node("iit-2", Utc::now());
+        high.confidence = 0.9;
+        high.content = "strongly held claim".to_string();
+        graph.add_concept(low);
+        graph.add_concept(high);
+
+        let digest = graph.confidence_weighted_content("IIT");
+        let strong_pos = digest.find("strongly held claim").unwrap();
+        let weak_pos = digest.find("weakly held claim").unwrap();
+        assert!(strong_pos < weak_pos);
+    }
+
+    #[test]
+    fn concepts_from_source_filters_by_provenance() {
+        let mut graph = MemoryGraph::new();
+        let mut from_paper = node("iit-1", Utc::now());
+        from_paper.provenance = Some("Tononi 2004".to_string());
+        let mut from_dialogue = node("iit-2", Utc::now());
+        from_dialogue.provenance = Some("dialogue:2026-08-08".to_string());
+        let mut unknown = node("iit-3", Utc::now());
+        unknown.provenance = None;
+
+        graph.add_concept(from_paper);
+        graph.add_concept(from_dialogue);
+        graph.add_concept(unknown);
+
+        let from_paper_concepts = graph.concepts_from_source("Tononi 2004");
+        assert_eq!(from_paper_concepts.len(), 1);
+        assert_eq!(from_paper_concepts[0].key, "iit-1");
+    }
+
+    #[test]
+    fn relation_confidence_histogram_buckets_by_tenths() {
+        let mut graph = MemoryGraph::new();
+        for key in ["a", "b", "c", "d"] {
+            graph.add_concept(node(key, Utc::now()));
+        }
+        for (from, to, strength) in [("a", "b", 0.05), ("b", "c", 0.95), ("c", "d", 0.92)] {
+            graph.relationships.push(SymbolicRelation {
+                from: from.to_string(),
+                to: to.to_string(),
+                relation_type: "related".to_string(),
+                strength,
+                last_updated: Utc::now(),
+            });
+        }
+
+        let histogram = graph.relation_confidence_histogram();
+        assert_eq!(histogram.len(), 10);
+        assert_eq!(histogram[0], (0.0, 1));
+        // Not `assert_eq!` against the literal `0.9`: `9 as f32 * 0.1` is
+        // `0.90000004`, not exactly `0.9`.
+        assert!((histogram[9].0 - 0.9).abs() < 1e-6);
+        assert_eq!(histogram[9].1, 2);
+    }
+
+    #[test]
+    fn most_confident_in_framework_picks_the_highest_confidence() {
+        let mut graph = MemoryGraph::new();
+        let mut low = node("iit-1", Utc::now());
+        low.confidence = 0.3;
+        let mut high = node("iit-2", Utc::now());
+        high.confidence = 0.9;
+        graph.add_concept(low);
+        graph.add_concept(high);
+
+        let top = graph.most_confident_in_framework("IIT").expect("framework has concepts");
+        assert_eq!(top.key, "iit-2");
+    }
+
+    #[test]
+    fn most_confident_in_framework_empty_returns_none() {
+        let graph = MemoryGraph::new();
+        assert!(graph.most_confident_in_framework("IIT").is_none());
+    }
+
+    #[test]
+    fn decay_relationships_weakens_stale_links_more_than_fresh_ones() {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(node("a", Utc::now()));
+        graph.add_concept(node("b", Utc::now()));
+        graph.add_concept(node("c", Utc::now()));
+
+        let now = Utc::now();
+        graph.relationships.push(SymbolicRelation {
+            from: "a".to_string(),
+            to: "b".to_string(),
+            relation_type: "supports".to_string(),
+            strength: 0.8,
+            last_updated: now - Duration::days(30),
+        });
+        graph.relationships.push(SymbolicRelation {
+            from: "b".to_string(),
+            to: "c".to_string(),
+            relation_type: "supports".to_string(),
+            strength: 0.8,
+            last_updated: now,
+        });
+
+        graph.decay_relationships(10.0, now);
+
+        let stale = graph.relationships[0].strength;
+        let fresh = graph.relationships[1].strength;
+        assert!(stale < 0.8 * 0.2, "stale relation should have decayed heavily, got {stale}");
+        assert!((fresh - 0.8).abs() < 1e-6, "fresh relation should barely move, got {fresh}");
+    }
+
+    #[test]
+    fn snapshot_and_restore_round_trip() {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(node("kurisu", Utc::now()));
+        let snapshot = graph.snapshot();
+
+        graph.delete_concept("kurisu");
+        assert!(graph.get_concept("kurisu").is_none());
+
+        graph.restore(snapshot);
+        assert!(graph.get_concept("kurisu").is_some());
+    }
+
+    #[test]
+    fn relationships_for_concepts_matches_per_concept_lookup() {
+        let mut graph = MemoryGraph::new();
+        for key in ["a", "b", "c"] {
+            graph.add_concept(node(key, Utc::now()));
+        }
+        graph.relationships.push(SymbolicRelation {
+            from: "a".to_string(),
+            to: "b".to_string(),
+            relation_type: "supports".to_string(),
+            strength: 0.5,
+            last_updated: Utc::now(),
+        });
+        graph.relationships.push(SymbolicRelation {
+            from: "b".to_string(),
+            to: "c".to_string(),
+            relation_type: "supports".to_string(),
+            strength: 0.5,
+            last_updated: Utc::now(),
+        });
+
+        let buckets = graph.relationships_for_concepts(&["a", "b"]);
+        assert_eq!(buckets["a"].len(), graph.get_relationships_for_concept("a").len());
+        assert_eq!(buckets["b"].len(), graph.get_relationships_for_concept("b").len());
+        assert_eq!(buckets["b"].len(), 2);
+    }
+
+    #[test]
+    fn frameworks_counts_and_orders_by_count_then_name() {
+        let mut graph = MemoryGraph::new();
+        for (key, framework) in [
+            ("iit-1", "IIT"),
+            ("iit-2", "IIT"),
+            ("gwt-1", "GWT"),
+            ("hot-1", "HOT"),
+        ] {
+            let mut n = node(key, Utc::now());
+            n.framework = framework.to_string();
+            graph.add_concept(n);
+        }
+
+        let frameworks = graph.frameworks();
+        assert_eq!(
+            frameworks,
+            vec![
+                ("IIT".to_string(), 2),
+                ("GWT".to_string(), 1),
+                ("HOT".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn concept_keys_lists_alphabetically() {
+        let mut graph = MemoryGraph::new();
+        for key in ["iit-2", "iit-1", "gwt-1"] {
+            graph.add_concept(node(key, Utc::now()));
+        }
+        assert_eq!(graph.concept_keys(), vec!["gwt-1".to_string(), "iit-1".to_string(), "iit-2".to_string()]);
+    }
+
+    #[test]
+    fn concepts_by_framework_filters_and_sorts_by_key() {
+        let mut graph = MemoryGraph::new();
+        for (key, framework) in [("iit-2", "IIT"), ("iit-1", "IIT"), ("gwt-1", "GWT")] {
+            let mut n = node(key, Utc::now());
+            n.framework = framework.to_string();
+            graph.add_concept(n);
+        }
+
+        let iit_keys: Vec<&str> = graph.concepts_by_framework("IIT").iter().map(|n| n.key.as_str()).collect();
+        assert_eq!(iit_keys, vec!["iit-1", "iit-2"]);
+        assert!(graph.concepts_by_framework("HOT").is_empty());
+    }
+
+    #[test]
+    fn confidence_distribution_is_all_zero_on_an_empty_graph() {
+        let graph = MemoryGraph::new();
+        let distribution = graph.confidence_distribution();
+        assert_eq!(distribution.min, 0.0);
+        assert_eq!(distribution.mean, 0.0);
+        assert_eq!(distribution.median, 0.0);
+        assert_eq!(distribution.max, 0.0);
+    }
+
+    #[test]
+    fn confidence_distribution_computes_min_mean_median_max() {
+        let mut graph = MemoryGraph::new();
+        for (key, confidence) in [("a", 0.2), ("b", 0.4), ("c", 0.6), ("d", 0.8)] {
+            let mut n = node(key, Utc::now());
+            n.confidence = confidence;
+            graph.add_concept(n);
+        }
+
+        let distribution = graph.confidence_distribution();
+        assert!((distribution.min - 0.2).abs() < 1e-6);
+        assert!((distribution.mean - 0.5).abs() < 1e-6);
+        assert!((distribution.median - 0.5).abs() < 1e-6);
+        assert!((distribution.max - 0.8).abs() < 1e-6);
+    }
+
+    #[test]
+    fn stats_reports_counts_and_breakdowns() {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(node("iit-1", Utc::now()));
+        let mut gwt = node("gwt-1", Utc::now());
+        gwt.framework = "GWT".to_string();
+        graph.add_concept(gwt);
+        graph.relationships.push(SymbolicRelation {
+            from: "iit-1".to_string(),
+            to: "gwt-1".to_string(),
+            relation_type: "supports".to_string(),
+            strength: 0.5,
+            last_updated: Utc::now(),
+        });
+
+        let stats = graph.stats();
+        assert_eq!(stats.concept_count, 2);
+        assert_eq!(stats.relationship_count, 1);
+        assert_eq!(stats.frameworks, graph.frameworks());
+        assert_eq!(stats.relation_types, vec![("supports".to_string(), 1)]);
+        assert_eq!(stats.confidence, graph.confidence_distribution());
+    }
+
+    #[test]
+    fn connected_components_groups_clusters_and_isolates() {
+        let mut graph = MemoryGraph::new();
+        for key in ["a", "b", "c", "d", "e"] {
+            graph.add_concept(node(key, Utc::now()));
+        }
+        // Cluster 1: a-b-c (3 concepts)
+        graph.relationships.push(SymbolicRelation {
+            from: "a".to_string(),
+            to: "b".to_string(),
+            relation_type: "related".to_string(),
+            strength: 0.5,
+            last_updated: Utc::now(),
+        });
+        graph.relationships.push(SymbolicRelation {
+            from: "b".to_string(),
+            to: "c".to_string(),
+            relation_type: "related".to_string(),
+            strength: 0.5,
+            last_updated: Utc::now(),
+        });
+        // Cluster 2: d-f (2 concepts)
+        graph.add_concept(node("f", Utc::now()));
+        graph.relationships.push(SymbolicRelation {
+            from: "d".to_string(),
+            to: "f".to_string(),
+            relation_type: "related".to_string(),
+            strength: 0.5,
+            last_updated: Utc::now(),
+        });
+        // "e" stays isolated.
+
+        let components = graph.connected_components();
+        assert_eq!(components.len(), 3);
+        assert_eq!(components[0], vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        assert_eq!(components[1], vec!["d".to_string(), "f".to_string()]);
+        assert_eq!(components[2], vec!["e".to_string()]);
+    }
+
+    #[test]
+    fn rescale_framework_confidence_rejects_negative_factor() {
+        let mut graph = MemoryGraph::new();
+        assert!(graph
+            .rescale_framework_confidence("IIT", -1.0, Utc::now())
+            .is_err());
+    }
+
+    fn graph_with_three_relations_on(key: &str) -> MemoryGraph {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(node(key, Utc::now()));
+        for other in ["a", "b", "c"] {
+            graph.add_concept(node(other, Utc::now()));
+            graph.relationships.push(SymbolicRelation {
+                from: key.to_string(),
+                to: other.to_string(),
+                relation_type: "related".to_string(),
+                strength: 0.5,
+                last_updated: Utc::now(),
+            });
+        }
+        graph
+    }
+
+    #[test]
+    fn remove_concept_cascades_to_its_relationships() {
+        let mut graph = graph_with_three_relations_on("iit-axiom-1");
+        let (removed, relations_removed) = graph.remove_concept("iit-axiom-1");
+        assert_eq!(removed.expect("concept existed").key, "iit-axiom-1");
+        assert_eq!(relations_removed, 3);
+        assert!(graph.relationships.is_empty());
+    }
+
+    #[test]
+    fn remove_concept_reports_none_and_zero_for_an_unknown_key() {
+        let mut graph = MemoryGraph::new();
+        let (removed, relations_removed) = graph.remove_concept("missing");
+        assert!(removed.is_none());
+        assert_eq!(relations_removed, 0);
+    }
+
+    #[test]
+    fn delete_concept_guarded_refuses_when_over_the_limit() {
+        let mut graph = graph_with_three_relations_on("iit-axiom-1");
+        let err = graph
+            .delete_concept_guarded("iit-axiom-1", 2)
+            .expect_err("3 relations exceeds max_relations of 2");
+        assert!(err.contains("3 relationships"));
+        assert!(graph.get_concept("iit-axiom-1").is_some());
+    }
+
+    #[test]
+    fn delete_concept_guarded_allows_when_under_the_limit() {
+        let mut graph = graph_with_three_relations_on("iit-axiom-1");
+        let deleted = graph
+            .delete_concept_guarded("iit-axiom-1", 5)
+            .expect("3 relations is within max_relations of 5");
+        assert_eq!(deleted.key, "iit-axiom-1");
+        assert!(graph.get_concept("iit-axiom-1").is_none());
+    }
+
+    #[test]
+    fn delete_concept_guarded_cascades_its_relationships_like_remove_concept() {
+        let mut graph = graph_with_three_relations_on("iit-axiom-1");
+        graph
+            .delete_concept_guarded("iit-axiom-1", 5)
+            .expect("3 relations is within max_relations of 5");
+        assert!(
+            graph.relationships.is_empty(),
+            "guarded delete must strip dependent relationships, not just the concept"
+        );
+    }
+
+    fn graph_with_varied_confidence() -> MemoryGraph {
+        let mut graph = MemoryGraph::new();
+        let mut high = node("confident", Utc::now());
+        high.confidence = 0.95;
+        graph.add_concept(high);
+
+        let mut low = node("shaky", Utc::now());
+        low.confidence = 0.05;
+        graph.add_concept(low);
+        graph
+    }
+
+    #[test]
+    fn sample_concepts_is_deterministic_for_a_fixed_seed() {
+        let graph = graph_with_varied_confidence();
+        let first = graph.sample_concepts(2, SampleWeight::Uniform, 42);
+        let second = graph.sample_concepts(2, SampleWeight::Uniform, 42);
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 2);
+    }
+
+    #[test]
+    fn inverse_confidence_weighting_over_samples_low_confidence_concepts() {
+        let graph = graph_with_varied_confidence();
+        let mut shaky_first_count = 0;
+        for seed in 0..200 {
+            let sample = graph.sample_concepts(1, SampleWeight::InverseConfidence, seed);
+            if sample == vec!["shaky".to_string()] {
+                shaky_first_count += 1;
+            }
+        }
+        assert!(
+            shaky_first_count > 150,
+            "expected inverse weighting to favor the low-confidence concept, got {shaky_first_count}/200"
+        );
+    }
+
+    #[test]
+    fn relationship_between_is_directional() {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(node("okabe", Utc::now()));
+        graph.add_concept(node("kurisu", Utc::now()));
+        graph.relationships.push(SymbolicRelation {
+            from: "okabe".to_string(),
+            to: "kurisu".to_string(),
+            relation_type: "trusts".to_string(),
+            strength: 0.8,
+            last_updated: Utc::now(),
+        });
+
+        assert_eq!(graph.relationship_between("okabe", "kurisu").len(), 1);
+        assert!(graph.relationship_between("kurisu", "okabe").is_empty());
+    }
+
+    #[test]
+    fn add_relationship_rejects_a_self_loop() {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(node("okabe", Utc::now()));
+
+        let err = graph
+            .add_relationship(SymbolicRelation {
+                from: "okabe".to_string(),
+                to: "okabe".to_string(),
+                relation_type: "trusts".to_string(),
+                strength: 0.8,
+                last_updated: Utc::now(),
+            })
+            .expect_err("self-loops are rejected");
+        assert!(err.contains("self-loop"));
+        assert!(graph.relationships.is_empty());
+    }
+
+    #[test]
+    fn add_relationship_rejects_out_of_range_strength() {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(node("okabe", Utc::now()));
+        graph.add_concept(node("kurisu", Utc::now()));
+
+        let err = graph
+            .add_relationship(SymbolicRelation {
+                from: "okabe".to_string(),
+                to: "kurisu".to_string(),
+                relation_type: "trusts".to_string(),
+                strength: 1.5,
+                last_updated: Utc::now(),
+            })
+            .expect_err("strength 1.5 is out of range");
+        assert!(err.contains("1.5"));
+        assert!(graph.relationships.is_empty());
+    }
+
+    #[test]
+    fn add_relationship_rejects_a_missing_endpoint() {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(node("okabe", Utc::now()));
+
+        let err = graph
+            .add_relationship(SymbolicRelation {
+                from: "okabe".to_string(),
+                to: "kurisu".to_string(),
+                relation_type: "trusts".to_string(),
+                strength: 0.8,
+                last_updated: Utc::now(),
+            })
+            .expect_err("'kurisu' doesn't exist yet");
+        assert!(err.contains("kurisu"));
+    }
+
+    #[test]
+    fn add_relationship_accepts_a_well_formed_relation() {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(node("okabe", Utc::now()));
+        graph.add_concept(node("kurisu", Utc::now()));
+
+        graph
+            .add_relationship(SymbolicRelation {
+                from: "okabe".to_string(),
+                to: "kurisu".to_string(),
+                relation_type: "trusts".to_string(),
+                strength: 0.8,
+                last_updated: Utc::now(),
+            })
+            .expect("well-formed relation is accepted");
+        assert_eq!(graph.relationships.len(), 1);
+    }
+
+    #[test]
+    fn add_relationship_updates_strength_in_place_instead_of_duplicating() {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(node("okabe", Utc::now()));
+        graph.add_concept(node("kurisu", Utc::now()));
+
+        graph
+            .add_relationship(SymbolicRelation {
+                from: "okabe".to_string(),
+                to: "kurisu".to_string(),
+                relation_type: "trusts".to_string(),
+                strength: 0.5,
+                last_updated: Utc::now(),
+            })
+            .expect("first add succeeds");
+        graph
+            .add_relationship(SymbolicRelation {
+                from: "okabe".to_string(),
+                to: "kurisu".to_string(),
+                relation_type: "trusts".to_string(),
+                strength: 0.9,
+                last_updated: Utc::now(),
+            })
+            .expect("second add succeeds");
+
+        let relations = graph.relationship_between("okabe", "kurisu");
+        assert_eq!(relations.len(), 1);
+        assert_eq!(relations[0].strength, 0.9);
+    }
+
+    #[test]
+    fn add_relationship_does_not_dedupe_across_different_relation_types() {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(node("okabe", Utc::now()));
+        graph.add_concept(node("kurisu", Utc::now()));
+
+        graph
+            .add_relationship(SymbolicRelation {
+                from: "okabe".to_string(),
+                to: "kurisu".to_string(),
+                relation_type: "trusts".to_string(),
+                strength: 0.5,
+                last_updated: Utc::now(),
+            })
+            .expect("first add succeeds");
+        graph
+            .add_relationship(SymbolicRelation {
+                from: "okabe".to_string(),
+                to: "kurisu".to_string(),
+                relation_type: "contradicts".to_string(),
+                strength: 0.3,
+                last_updated: Utc::now(),
+            })
+            .expect("second add succeeds");
+
+        assert_eq!(graph.relationship_between("okabe", "kurisu").len(), 2);
+    }
+
+    #[test]
+    fn are_related_is_symmetric() {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(node("okabe", Utc::now()));
+        graph.add_concept(node("kurisu", Utc::now()));
+        graph.relationships.push(SymbolicRelation {
+            from: "okabe".to_string(),
+            to: "kurisu".to_string(),
+            relation_type: "trusts".to_string(),
+            strength: 0.8,
+            last_updated: Utc::now(),
+        });
+
+        assert!(graph.are_related("okabe", "kurisu"));
+        assert!(graph.are_related("kurisu", "okabe"));
+        assert!(!graph.are_related("okabe", "daru"));
+    }
+
+    #[test]
+    fn validate_finds_dangling_relations() {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(node("okabe", Utc::now()));
+        graph.relationships.push(SymbolicRelation {
+            from: "okabe".to_string(),
+            to: "kurisu".to_string(),
+            relation_type: "trusts".to_string(),
+            strength: 0.8,
+            last_updated: Utc::now(),
+        });
+
+        let issues = graph.validate();
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].contains("kurisu"));
+    }
+
+    #[test]
+    fn validate_on_save_refuses_to_write_a_dangling_relation_introduced_via_deserialization() {
+        let json = r#"{
+            "concepts": {
+                "okabe": {
+                    "key": "okabe",
+                    "content": "reading steiner",
+                    "confidence": 0.9,
+                    "framework": "sci-fi",
+                    "last_updated": "2026-01-01T00:00:00Z"
+                }
+            },
+            "relationships": [
+                {
+                    "from": "okabe",
+                    "to": "missing-concept",
+                    "relation_type": "trusts",
+                    "strength": 0.8,
+                    "last_updated": "2026-01-01T00:00:00Z"
+                }
+            ]
+        }"#;
+        let mut graph: MemoryGraph = serde_json::from_str(json).expect("valid graph JSON");
+        graph.set_validate_on_save(true);
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("graph.json");
+        let result = graph.save(&path);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("missing-concept"));
+        assert!(!path.exists());
+    }
+
+    fn graph_with_contradiction(from_confidence: f32, to_confidence: f32) -> MemoryGraph {
+        let mut graph = MemoryGraph::new();
+        let mut from = node("many-worlds", Utc::now());
+        from.confidence = from_confidence;
+        graph.add_concept(from);
+        let mut to = node("copenhagen", Utc::now());
+        to.confidence = to_confidence;
+        graph.add_concept(to);
+        graph
+            .add_relationship(SymbolicRelation {
+                from: "many-worlds".to_string(),
+                to: "copenhagen".to_string(),
+                relation_type: "contradicts".to_string(),
+                strength: 0.9,
+                last_updated: Utc::now(),
+            })
+            .expect("both endpoints exist");
+        graph
+    }
+
+    #[test]
+    fn contradictions_reports_the_contradicting_pair() {
+        let graph = graph_with_contradiction(0.9, 0.9);
+        assert_eq!(graph.contradictions(), vec![("many-worlds".to_string(), "copenhagen".to_string())]);
+    }
+
+    #[test]
+    fn is_consistent_is_false_when_both_contradicting_concepts_are_high_confidence() {
+        let graph = graph_with_contradiction(0.9, 0.8);
+        assert!(!graph.is_consistent());
+    }
+
+    #[test]
+    fn is_consistent_is_true_when_a_contradicting_concept_is_low_confidence() {
+        let graph = graph_with_contradiction(0.9, 0.3);
+        assert!(graph.is_consistent());
+    }
+
+    #[test]
+    fn is_consistent_is_true_with_no_contradictions() {
+        let graph = chain_graph(&["a", "b"]);
+        assert!(graph.is_consistent());
+    }
+
+    #[test]
+    fn recently_updated_sorts_newest_first_and_truncates_to_limit() {
+        let mut graph = MemoryGraph::new();
+        let base = Utc::now();
+        graph.add_concept(node("oldest", base));
+        graph.add_concept(node("middle", base + Duration::seconds(60)));
+        graph.add_concept(node("newest", base + Duration::seconds(120)));
+
+        let top: Vec<&str> = graph.recently_updated(2).into_iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(top, vec!["newest", "middle"]);
+    }
+
+    fn chain_graph(keys: &[&str]) -> MemoryGraph {
+        let mut graph = MemoryGraph::new();
+        for key in keys {
+            graph.add_concept(node(key, Utc::now()));
+        }
+        for pair in keys.windows(2) {
+            graph
+                .add_relationship(SymbolicRelation {
+                    from: pair[0].to_string(),
+                    to: pair[1].to_string(),
+                    relation_type: "next".to_string(),
+                    strength: 0.5,
+                    last_updated: Utc::now(),
+                })
+                .expect("both endpoints exist");
+        }
+        graph
+    }
+
+    #[test]
+    fn concepts_within_stops_at_max_depth() {
+        let graph = chain_graph(&["a", "b", "c", "d", "e"]);
+        let mut reached = graph.concepts_within("a", 2);
+        reached.sort_by_key(|(_, depth)| *depth);
+        assert_eq!(
+            reached,
+            vec![("a".to_string(), 0), ("b".to_string(), 1), ("c".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn concepts_within_follows_relationships_in_either_direction() {
+        let graph = chain_graph(&["a", "b", "c"]);
+        let reached: HashSet<String> = graph.concepts_within("c", 2).into_iter().map(|(key, _)| key).collect();
+        assert_eq!(reached, HashSet::from(["c".to_string(), "b".to_string(), "a".to_string()]));
+    }
+
+    #[test]
+    fn concepts_within_is_empty_for_an_unknown_start() {
+        let graph = chain_graph(&["a", "b"]);
+        assert!(graph.concepts_within("missing", 5).is_empty());
+    }
+
+    fn graph_with_confidence_spread() -> MemoryGraph {
+        let mut graph = MemoryGraph::new();
+        for (key, confidence) in [("low", 0.2), ("mid-low", 0.4), ("mid-high", 0.6), ("high", 0.9)] {
+            let mut n = node(key, Utc::now());
+            n.confidence = confidence;
+            graph.add_concept(n);
+        }
+        graph
+    }
+
+    #[test]
+    fn concepts_below_confidence_sorts_ascending() {
+        let graph = graph_with_confidence_spread();
+        let below: Vec<&str> = graph.concepts_below_confidence(0.5).into_iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(below, vec!["low", "mid-low"]);
+    }
+
+    #[test]
+    fn concepts_above_confidence_sorts_descending() {
+        let graph = graph_with_confidence_spread();
+        let above: Vec<&str> = graph.concepts_above_confidence(0.5).into_iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(above, vec!["high", "mid-high"]);
+    }
+
+    #[test]
+    fn set_metadata_survives_a_save_load_round_trip() {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(node("iit-axiom-1", Utc::now()));
+        graph.set_metadata("iit-axiom-1", "source", "Tononi 2004").expect("concept exists");
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("graph.json");
+        graph.save(&path).expect("save succeeds");
+
+        let loaded = MemoryGraph::load(&path).expect("load succeeds");
+        assert_eq!(loaded.get_metadata("iit-axiom-1", "source"), Some("Tononi 2004"));
+    }
+
+    #[test]
+    fn get_metadata_is_none_for_an_unknown_concept_or_tag() {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(node("iit-axiom-1", Utc::now()));
+        assert_eq!(graph.get_metadata("iit-axiom-1", "source"), None);
+        assert_eq!(graph.get_metadata("missing", "source"), None);
+    }
+
+    #[test]
+    fn set_metadata_errors_for_an_unknown_concept() {
+        let mut graph = MemoryGraph::new();
+        assert!(graph.set_metadata("missing", "source", "x").is_err());
+    }
+
+    #[test]
+    fn save_without_validate_on_save_writes_even_a_dangling_relation() {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(node("okabe", Utc::now()));
+        graph.relationships.push(SymbolicRelation {
+            from: "okabe".to_string(),
+            to: "missing-concept".to_string(),
+            relation_type: "trusts".to_string(),
+            strength: 0.8,
+            last_updated: Utc::now(),
+        });
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("graph.json");
+        graph.save(&path).expect("save succeeds without validate_on_save");
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn suggest_merges_only_reports_the_pair_above_the_threshold() {
+        let mut graph = MemoryGraph::new();
+        let mut a = node("divergence-meter", Utc::now());
+        a.content = "measures the world line divergence number".to_string();
+        let mut b = node("divergence-gauge", Utc::now());
+        b.content = "measures the world line divergence value".to_string();
+        let mut c = node("time-leap-machine", Utc::now());
+        c.content = "sends memories back in time".to_string();
+        graph.add_concept(a);
+        graph.add_concept(b);
+        graph.add_concept(c);
+
+        let suggestions = graph.suggest_merges(0.5);
+
+        assert_eq!(suggestions.len(), 1);
+        let (x, y, similarity) = &suggestions[0];
+        assert_eq!((x.as_str(), y.as_str()), ("divergence-gauge", "divergence-meter"));
+        assert!(*similarity > 0.5);
+    }
+
+    #[test]
+    fn builder_defaults_confidence_and_last_updated() {
+        let before = Utc::now();
+        let node = SymbolicNode::builder("iit-1")
+            .content("consciousness requires integration")
+            .framework("IIT")
+            .build()
+            .expect("default confidence is valid");
+
+        assert_eq!(node.key, "iit-1");
+        assert_eq!(node.content, "consciousness requires integration");
+        assert_eq!(node.framework, "IIT");
+        assert_eq!(node.confidence, 0.5);
+        assert!(node.last_updated >= before);
+    }
+
+    #[test]
+    fn builder_honors_an_explicit_confidence() {
+        let node = SymbolicNode::builder("iit-1").confidence(0.9).build().expect("0.9 is valid");
+        assert_eq!(node.confidence, 0.9);
+    }
+
+    #[test]
+    fn builder_rejects_out_of_range_confidence() {
+        assert!(SymbolicNode::builder("iit-1").confidence(1.5).build().is_err());
+        assert!(SymbolicNode::builder("iit-1").confidence(-0.1).build().is_err());
+    }
+}
@@ -0,0 +1,2204 @@
+//! Suggested repo path: src/proof_engine.rs
+//!
+//! Drives external theorem provers (Lean, Z3, ...) to check formal
+//! statements, and tracks enough bookkeeping (status, statement ids) for
+//! those checks to be referenced elsewhere in the system, e.g. recorded as
+//! concepts in the `MemoryGraph`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::memory_graph::{MemoryGraph, SymbolicNode, SymbolicRelation};
+
+/// A small counting semaphore used to cap how many prover subprocesses run
+/// at once, so batch/consensus verification doesn't fork-bomb the machine
+/// with dozens of Z3 processes.
+struct Semaphore {
+    state: Mutex<usize>,
+    condvar: Condvar,
+}
+
+impl Semaphore {
+    fn new(permits: usize) -> Self {
+        Self {
+            state: Mutex::new(permits),
+            condvar: Condvar::new(),
+        }
+    }
+
+    fn acquire(&self) {
+        let mut available = self.state.lock().unwrap();
+        while *available == 0 {
+            available = self.condvar.wait(available).unwrap();
+        }
+        *available -= 1;
+    }
+
+    fn release(&self) {
+        let mut available = self.state.lock().unwrap();
+        *available += 1;
+        self.condvar.notify_one();
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProofStatus {
+    Proved,
+    Disproved,
+    /// The prover subprocess ran past its deadline without producing a
+    /// result. Distinct from `Error` so callers can decide to retry a
+    /// timeout without retrying a genuine failure.
+    Timeout,
+    /// The prover ran to completion but couldn't determine truth or
+    /// falsity of the statement - it's outside the decidable fragment the
+    /// backend supports. The `String` carries the reason.
+    Undecidable(String),
+    Error(String),
+}
+
+impl fmt::Display for ProofStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProofStatus::Proved => write!(f, "proven"),
+            ProofStatus::Disproved => write!(f, "disproven"),
+            ProofStatus::Timeout => write!(f, "timeout"),
+            ProofStatus::Undecidable(reason) => write!(f, "undecidable: {reason}"),
+            ProofStatus::Error(message) => write!(f, "error: {message}"),
+        }
+    }
+}
+
+impl std::str::FromStr for ProofStatus {
+    type Err = String;
+
+    /// Parse the `Display` forms back into a `ProofStatus`, case-
+    /// insensitively. `"error"` alone (with no `: message` suffix) parses
+    /// to an `Error` with an empty message. Unrecognized input, including
+    /// statuses not yet implemented by this enum (e.g. "in_progress",
+    /// "contradiction"), is rejected with a descriptive error.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        let lower = trimmed.to_lowercase();
+        match lower.as_str() {
+            "proven" => Ok(ProofStatus::Proved),
+            "disproven" => Ok(ProofStatus::Disproved),
+            "timeout" => Ok(ProofStatus::Timeout),
+            "undecidable" => Ok(ProofStatus::Undecidable(String::new())),
+            _ if lower.starts_with("undecidable:") => {
+                Ok(ProofStatus::Undecidable(trimmed[12..].trim().to_string()))
+            }
+            "error" => Ok(ProofStatus::Error(String::new())),
+            _ if lower.starts_with("error:") => {
+                Ok(ProofStatus::Error(trimmed[6..].trim().to_string()))
+            }
+            _ => Err(format!("unrecognized proof status: '{s}'")),
+        }
+    }
+}
+
+/// Why a `ProofEngine` operation failed, as a matchable alternative to a
+/// bare `String` - so callers can e.g. retry on `ProverNotFound` after
+/// registering a prover, without having to pattern-match message text.
+/// `Display`'s wording stays close to the plain-string errors this
+/// replaced, so existing `.to_string().contains(...)` checks keep working.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProofError {
+    /// No provers are registered on this engine at all.
+    NoProversAvailable,
+    /// No registered prover has this name.
+    ProverNotFound(String),
+    /// A prover subprocess, or a cache read/write, failed to complete.
+    ExecutionFailed(String),
+    /// A statement template couldn't be resolved into a concrete statement.
+    TranslationFailed(String),
+}
+
+impl fmt::Display for ProofError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProofError::NoProversAvailable => write!(f, "no provers registered"),
+            ProofError::ProverNotFound(name) => write!(f, "no registered prover named '{name}'"),
+            ProofError::ExecutionFailed(message) => write!(f, "{message}"),
+            ProofError::TranslationFailed(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for ProofError {}
+
+/// A term in a `Formula`: either a variable reference or an integer
+/// literal.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    Var(String),
+    Int(i64),
+}
+
+impl Term {
+    fn to_smtlib2(&self) -> String {
+        match self {
+            Term::Var(name) => name.clone(),
+            Term::Int(n) => n.to_string(),
+        }
+    }
+
+    fn to_lean(&self) -> String {
+        match self {
+            Term::Var(name) => name.clone(),
+            Term::Int(n) => n.to_string(),
+        }
+    }
+}
+
+/// A small first-order formula, as a structured alternative to the
+/// free-form natural-language strings `verify`/`verify_statement` deal in.
+/// A prover with a faithful translation target (Z3's SMT-LIB2, Lean's term
+/// syntax) can render one of these directly via `verify_formula` instead of
+/// pattern-matching ad hoc statement text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Formula {
+    ForAll(String, Box<Formula>),
+    Exists(String, Box<Formula>),
+    Eq(Term, Term),
+    Gt(Term, Term),
+    And(Box<Formula>, Box<Formula>),
+    Or(Box<Formula>, Box<Formula>),
+    Not(Box<Formula>),
+}
+
+impl Formula {
+    /// Render as an SMT-LIB2 expression, e.g. for `(assert ...)` in a Z3
+    /// query. Quantified variables are declared as `Int`, the only sort
+    /// this fragment needs.
+    pub fn to_smtlib2(&self) -> String {
+        match self {
+            Formula::ForAll(var, body) => format!("(forall (({var} Int)) {})", body.to_smtlib2()),
+            Formula::Exists(var, body) => format!("(exists (({var} Int)) {})", body.to_smtlib2()),
+            Formula::Eq(a, b) => format!("(= {} {})", a.to_smtlib2(), b.to_smtlib2()),
+            Formula::Gt(a, b) => format!("(> {} {})", a.to_smtlib2(), b.to_smtlib2()),
+            Formula::And(a, b) => format!("(and {} {})", a.to_smtlib2(), b.to_smtlib2()),
+            Formula::Or(a, b) => format!("(or {} {})", a.to_smtlib2(), b.to_smtlib2()),
+            Formula::Not(a) => format!("(not {})", a.to_smtlib2()),
+        }
+    }
+
+    /// Render as a Lean term, in the same plain-ASCII style as the rest of
+    /// this crate's example statements (e.g. `"forall n, n + 0 = n"`).
+    pub fn to_lean(&self) -> String {
+        match self {
+            Formula::ForAll(var, body) => format!("forall {var}, {}", body.to_lean()),
+            Formula::Exists(var, body) => format!("exists {var}, {}", body.to_lean()),
+            Formula::Eq(a, b) => format!("{} = {}", a.to_lean(), b.to_lean()),
+            Formula::Gt(a, b) => format!("{} > {}", a.to_lean(), b.to_lean()),
+            Formula::And(a, b) => format!("{} /\\ {}", a.to_lean(), b.to_lean()),
+            Formula::Or(a, b) => format!("{} \\/ {}", a.to_lean(), b.to_lean()),
+            Formula::Not(a) => format!("not ({})", a.to_lean()),
+        }
+    }
+}
+
+/// A first-order logic fragment a prover can decide, named after the
+/// closest SMT-LIB logic where one exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Logic {
+    /// Quantifier-free uninterpreted functions.
+    QfUf,
+    LinearArithmetic,
+    NonlinearArithmetic,
+    /// Full first-order logic, as a general-purpose proof assistant (Lean,
+    /// Coq) handles rather than a decision procedure.
+    FullFirstOrder,
+}
+
+/// A rough, qualitative sense of how long a prover takes to return on a
+/// typical statement - not a timing guarantee, just enough to prefer a
+/// fast decision procedure over a slow interactive one when either would do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpeedClass {
+    Fast,
+    Moderate,
+    Slow,
+}
+
+/// What a `TheoremProver` can decide: which logics it supports, whether it
+/// handles quantifiers, and its rough speed class. Lets a caller (or
+/// `ProofEngine`) pick a capable prover for a statement instead of just
+/// trying the first one registered.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProverCapabilities {
+    pub logics: Vec<Logic>,
+    pub supports_quantifiers: bool,
+    pub speed: SpeedClass,
+}
+
+/// A theorem prover backend (Lean, Z3, Coq, ...) the engine can dispatch
+/// statements to. `Send + Sync` so `verify_consensus` can run every
+/// registered prover on its own thread.
+pub trait TheoremProver: Send + Sync {
+    fn name(&self) -> &str;
+    fn verify(&self, statement: &str) -> ProofStatus;
+
+    /// Verify a structured `Formula` instead of a free-form statement
+    /// string. Defaults to rendering it via `Formula::to_lean` and handing
+    /// that to `verify`, which is a reasonable fallback for any backend
+    /// that accepts plain statement text; backends with a more faithful
+    /// native syntax (SMT-LIB2 for Z3, Lean's own term syntax) should
+    /// override this to render accordingly instead.
+    fn verify_formula(&self, formula: &Formula) -> ProofStatus {
+        self.verify(&formula.to_lean())
+    }
+
+    /// What this prover can decide - see `ProverCapabilities`. Defaults to
+    /// the most conservative fragment (quantifier-free uninterpreted
+    /// functions only, moderate speed), so a generic or test prover doesn't
+    /// overclaim what it can handle; provers with a real backend should
+    /// override this with its actual capabilities.
+    fn capabilities(&self) -> ProverCapabilities {
+        ProverCapabilities {
+            logics: vec![Logic::QfUf],
+            supports_quantifiers: false,
+            speed: SpeedClass::Moderate,
+        }
+    }
+
+    /// Whether this prover's backend is currently usable (e.g. its binary
+    /// is on `PATH`). Checking this can be expensive (filesystem stats, a
+    /// `--version` subprocess), so `ProofEngine` caches the result - see
+    /// `set_availability_cache_ttl`. Defaults to always available; provers
+    /// backed by an external tool should override this.
+    fn is_available(&self) -> bool {
+        true
+    }
+}
+
+/// A `TheoremProver` that always returns a fixed, caller-chosen status,
+/// regardless of the statement. Exposed publicly so downstream crates can
+/// exercise `ProofEngine` in their own tests without shelling out to a
+/// real prover.
+pub struct MockProver {
+    name: String,
+    status: ProofStatus,
+}
+
+impl MockProver {
+    pub fn new(name: &str, status: ProofStatus) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+        }
+    }
+}
+
+/// Default timeout for `ExternalProver` and `LeanProver`, chosen to be
+/// generous for a real solver while still bounding a looping one.
+const DEFAULT_PROVER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Spawn `command`, polling for completion until `timeout` elapses. If it
+/// hasn't finished by then, it's killed and a `Timeout` status is
+/// returned instead of hanging forever on a solver that loops.
+fn run_with_timeout(mut command: std::process::Command, timeout: Duration) -> Result<std::process::Output, ProofStatus> {
+    let mut child = match command
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => return Err(ProofStatus::Error(e.to_string())),
+    };
+
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => {
+                return child
+                    .wait_with_output()
+                    .map_err(|e| ProofStatus::Error(e.to_string()));
+            }
+            Ok(None) => {
+                if started.elapsed() >= timeout {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(ProofStatus::Timeout);
+                }
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            Err(e) => return Err(ProofStatus::Error(e.to_string())),
+        }
+    }
+}
+
+/// A `TheoremProver` backed by an external command-line tool: `verify`
+/// shells out to `binary` with the statement as its argument, treating a
+/// zero exit code as proved and a nonzero one as disproved. The
+/// subprocess is killed and reported `Timeout` if it runs past
+/// `timeout`.
+pub struct ExternalProver {
+    name: String,
+    binary: String,
+    timeout: Duration,
+    /// Extra flags inserted before the statement argument in every
+    /// invocation, e.g. `["-smt2"]` for a Z3 installation that needs it.
+    extra_args: Vec<String>,
+}
+
+impl ExternalProver {
+    pub fn new(name: &str, binary: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            binary: binary.to_string(),
+            timeout: DEFAULT_PROVER_TIMEOUT,
+            extra_args: Vec::new(),
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_extra_args(mut self, extra_args: Vec<String>) -> Self {
+        self.extra_args = extra_args;
+        self
+    }
+}
+
+impl TheoremProver for ExternalProver {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn verify(&self, statement: &str) -> ProofStatus {
+        let mut command = std::process::Command::new(&self.binary);
+        command.args(&self.extra_args);
+        command.arg(statement);
+        match run_with_timeout(command, self.timeout) {
+            Ok(output) if output.status.success() => ProofStatus::Proved,
+            Ok(_) => ProofStatus::Disproved,
+            Err(status) => status,
+        }
+    }
+
+    fn is_available(&self) -> bool {
+        std::process::Command::new(&self.binary)
+            .arg("--version")
+            .output()
+            .is_ok()
+    }
+
+    /// Z3-flavored `ExternalProver`s (`self.name == "z3"`) render `formula`
+    /// as a faithful SMT-LIB2 query instead of falling back to Lean-style
+    /// text; any other `name` falls back to the trait default, since a
+    /// generic external binary has no established native syntax here.
+    fn verify_formula(&self, formula: &Formula) -> ProofStatus {
+        if self.name == "z3" {
+            self.verify(&format!("(assert {})\n(check-sat)", formula.to_smtlib2()))
+        } else {
+            self.verify(&formula.to_lean())
+        }
+    }
+
+    /// A Z3-flavored `ExternalProver` (`self.name == "z3"`) reports Z3's
+    /// actual capabilities: linear and nonlinear arithmetic plus
+    /// uninterpreted functions, full quantifier support, and a fast
+    /// decision-procedure speed class. Any other `name` falls back to the
+    /// conservative trait default, since a generic external binary's
+    /// capabilities aren't known here.
+    fn capabilities(&self) -> ProverCapabilities {
+        if self.name == "z3" {
+            ProverCapabilities {
+                logics: vec![Logic::QfUf, Logic::LinearArithmetic, Logic::NonlinearArithmetic],
+                supports_quantifiers: true,
+                speed: SpeedClass::Fast,
+            }
+        } else {
+            ProverCapabilities {
+                logics: vec![Logic::QfUf],
+                supports_quantifiers: false,
+                speed: SpeedClass::Moderate,
+            }
+        }
+    }
+}
+
+/// A `TheoremProver` backed by the `lean` binary. Unlike `ExternalProver`,
+/// it parses stderr on a nonzero exit to tell a genuine proof failure
+/// ("unsolved goals", "type mismatch" - the statement is false or
+/// unprovable as written) apart from a compilation/elaboration error
+/// (anything else Lean reports, e.g. a syntax error or missing import),
+/// since only the former should count as `Disproved`. The subprocess is
+/// killed and reported `Timeout` if it runs past `timeout`.
+pub struct LeanProver {
+    binary: String,
+    timeout: Duration,
+    /// Extra flags inserted before the statement argument in every
+    /// invocation, e.g. `["--quiet"]`.
+    extra_args: Vec<String>,
+}
+
+impl LeanProver {
+    pub fn new(binary: &str) -> Self {
+        Self {
+            binary: binary.to_string(),
+            timeout: DEFAULT_PROVER_TIMEOUT,
+            extra_args: Vec::new(),
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_extra_args(mut self, extra_args: Vec<String>) -> Self {
+        self.extra_args = extra_args;
+        self
+    }
+}
+
+impl TheoremProver for LeanProver {
+    fn name(&self) -> &str {
+        "lean"
+    }
+
+    fn verify(&self, statement: &str) -> ProofStatus {
+        let mut command = std::process::Command::new(&self.binary);
+        command.args(&self.extra_args);
+        command.arg(statement);
+        match run_with_timeout(command, self.timeout) {
+            Ok(output) => classify_lean_output(output.status.success(), &String::from_utf8_lossy(&output.stderr)),
+            Err(status) => status,
+        }
+    }
+
+    fn is_available(&self) -> bool {
+        std::process::Command::new(&self.binary)
+            .arg("--version")
+            .output()
+            .is_ok()
+    }
+
+    /// Lean is a full proof assistant: it handles arbitrary quantified
+    /// first-order statements, but as an interactive elaborator rather
+    /// than a decision procedure, it's slower than Z3 on the fragment they
+    /// both cover.
+    fn capabilities(&self) -> ProverCapabilities {
+        ProverCapabilities {
+            logics: vec![Logic::FullFirstOrder],
+            supports_quantifiers: true,
+            speed: SpeedClass::Slow,
+        }
+    }
+}
+
+/// Classify a Lean invocation's outcome: `success` is its exit status,
+/// `stderr` its captured error output. Pulled out of `LeanProver::verify`
+/// so the classification logic can be exercised with mock Lean output
+/// directly, without shelling out to a real binary.
+fn classify_lean_output(success: bool, stderr: &str) -> ProofStatus {
+    if success {
+        return ProofStatus::Proved;
+    }
+
+    let lower = stderr.to_lowercase();
+    if lower.contains("unsolved goals") || lower.contains("type mismatch") {
+        ProofStatus::Disproved
+    } else {
+        ProofStatus::Error(format!("Lean elaboration error: {}", stderr.trim()))
+    }
+}
+
+/// A `TheoremProver` backed by the `coqc` binary. Coq has no equivalent of
+/// passing a bare statement on the command line - it compiles `.v` source
+/// files - so `verify` wraps `statement` in a trivial `Theorem ... Qed`
+/// shell, writes it to a temp file, and runs `coqc` on that. Otherwise
+/// mirrors `LeanProver`: stderr is parsed on a nonzero exit to tell a
+/// genuine proof failure apart from a compilation error, and the
+/// subprocess is killed and reported `Timeout` if it runs past
+/// `timeout`.
+pub struct CoqProver {
+    executable_path: String,
+    timeout: Duration,
+    /// Extra flags inserted before the source file argument in every
+    /// invocation, e.g. `["-q"]`.
+    extra_args: Vec<String>,
+}
+
+impl CoqProver {
+    pub fn new(executable_path: &str) -> Self {
+        Self {
+            executable_path: executable_path.to_string(),
+            timeout: DEFAULT_PROVER_TIMEOUT,
+            extra_args: Vec::new(),
+        }
+    }
+
+    /// Look for `coqc` at the handful of places a typical install puts it,
+    /// returning the first one found. Unlike `ExternalProver`/`LeanProver`,
+    /// which rely on `PATH`, this checks fixed filesystem locations
+    /// directly, since Coq installs (notably on Windows) are often not on
+    /// `PATH` by default.
+    pub fn auto_detect() -> Option<Self> {
+        const CANDIDATES: &[&str] = &[
+            "/usr/bin/coqc",
+            "/usr/local/bin/coqc",
+            "C:\\Coq\\bin\\coqc.exe",
+            "C:\\Program Files\\Coq\\bin\\coqc.exe",
+        ];
+        CANDIDATES
+            .iter()
+            .find(|candidate| Path::new(candidate).exists())
+            .map(|candidate| Self::new(candidate))
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_extra_args(mut self, extra_args: Vec<String>) -> Self {
+        self.extra_args = extra_args;
+        self
+    }
+
+    /// Wrap `statement` as the goal of a trivial Coq theorem, closed with
+    /// `auto` - enough for simple shapes such as the built-in `reflexivity`
+    /// template (`x = x`). The built-in `ordering` template joins its sides
+    /// with a literal `or`, which is not Coq's disjunction syntax. That,
+    /// like anything else outside this fragment, fails to elaborate and
+    /// comes back as an `Error`, not a `Disproved`.
+    fn translate_to_coq(statement: &str) -> String {
+        format!("Theorem generated_goal : {statement}.\nProof.\n  auto.\nQed.\n")
+    }
+}
+
+impl TheoremProver for CoqProver {
+    fn name(&self) -> &str {
+        "coq"
+    }
+
+    /// `tempfile::tempdir()` gives every invocation its own uniquely-named
+    /// directory (and removes it on drop), so concurrent `verify` calls on
+    /// the same `CoqProver` never clobber each other's `generated_goal.v` -
+    /// no process id or counter needed on top of it.
+    fn verify(&self, statement: &str) -> ProofStatus {
+        let dir = match tempfile::tempdir() {
+            Ok(dir) => dir,
+            Err(e) => return ProofStatus::Error(e.to_string()),
+        };
+        let source_path = dir.path().join("generated_goal.v");
+        if let Err(e) = fs::write(&source_path, Self::translate_to_coq(statement)) {
+            return ProofStatus::Error(e.to_string());
+        }
+
+        let mut command = std::process::Command::new(&self.executable_path);
+        command.args(&self.extra_args);
+        command.arg(&source_path);
+        match run_with_timeout(command, self.timeout) {
+            Ok(output) => classify_coq_output(output.status.success(), &String::from_utf8_lossy(&output.stderr)),
+            Err(status) => status,
+        }
+    }
+
+    fn is_available(&self) -> bool {
+        std::process::Command::new(&self.executable_path)
+            .arg("--version")
+            .output()
+            .is_ok()
+    }
+
+    /// Coq, like Lean, is a full proof assistant handling arbitrary
+    /// quantified first-order statements, slower than a decision procedure
+    /// on the fragment they both cover.
+    fn capabilities(&self) -> ProverCapabilities {
+        ProverCapabilities {
+            logics: vec![Logic::FullFirstOrder],
+            supports_quantifiers: true,
+            speed: SpeedClass::Slow,
+        }
+    }
+}
+
+/// Classify a Coq invocation's outcome: `success` is its exit status,
+/// `stderr` its captured error output. Pulled out of `CoqProver::verify` so
+/// the classification logic can be exercised with mock Coq output
+/// directly, without shelling out to a real binary.
+fn classify_coq_output(success: bool, stderr: &str) -> ProofStatus {
+    if success {
+        return ProofStatus::Proved;
+    }
+
+    let lower = stderr.to_lowercase();
+    if lower.contains("unable to unify") || lower.contains("tactic failure") || lower.contains("no applicable tactic") {
+        ProofStatus::Disproved
+    } else {
+        ProofStatus::Error(format!("Coq elaboration error: {}", stderr.trim()))
+    }
+}
+
+impl TheoremProver for MockProver {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn verify(&self, _statement: &str) -> ProofStatus {
+        self.status.clone()
+    }
+}
+
+pub struct ProofEngine {
+    provers: Vec<Box<dyn TheoremProver + Send + Sync>>,
+    /// Name of the preferred prover for `verify_statement(stmt, None)`,
+    /// used when still available; otherwise we fall back to the first
+    /// registered prover.
+    default_prover: Option<String>,
+    /// Maps a statement's canonical id back to the original statement text,
+    /// so external systems that only have the id can recover it.
+    statement_ids: HashMap<String, String>,
+    /// Caps how many prover subprocesses run concurrently during
+    /// batch/consensus operations.
+    concurrency_limit: Arc<Semaphore>,
+    /// How long a cached `is_available` result stays valid. `None` means
+    /// always re-check (the pre-caching behavior).
+    availability_cache_ttl: Option<Duration>,
+    /// Cached `(checked_at, available)` per prover name.
+    availability_cache: Mutex<HashMap<String, (Instant, bool)>>,
+    /// Reusable parametric statement templates, keyed by name, with
+    /// `{var}` placeholders to be filled in by `verify_template`.
+    templates: HashMap<String, String>,
+    /// Caches `verify_with_any_prover` and `verify_statement` results by
+    /// statement, so repeated verification of the same statement doesn't
+    /// re-invoke the provers. Bounded by `ProofCache::capacity`; `None`
+    /// means it grows without bound until `clear_cache`/`remove_cached` is
+    /// called - useful when a prover's environment changes and a stale
+    /// result (e.g. a previous `Undecidable`) should be retried.
+    proof_cache: Mutex<ProofCache>,
+    /// How many times a single prover invocation is attempted before
+    /// giving up, retrying only `ProofStatus::Error` results (transient
+    /// I/O failures) with exponential backoff - see `with_retry`. `1`
+    /// means no retries, the pre-existing behavior.
+    retry_max_attempts: usize,
+    /// The delay before the first retry; each subsequent retry doubles it.
+    retry_base_delay: Duration,
+}
+
+/// A statement-keyed cache of `ProofResult`s, bounded by an optional LRU
+/// capacity. `get` and `insert` both count as an access that refreshes a
+/// statement's recency, so the least-recently-*used* (not least-recently-
+/// inserted) entry is the one evicted when the cache is full.
+struct ProofCache {
+    entries: HashMap<String, ProofResult>,
+    /// Statement keys ordered from least- to most-recently used.
+    recency: Vec<String>,
+    capacity: Option<usize>,
+}
+
+impl ProofCache {
+    fn new(capacity: Option<usize>) -> Self {
+        Self {
+            entries: HashMap::new(),
+            recency: Vec::new(),
+            capacity,
+        }
+    }
+
+    fn touch(&mut self, statement: &str) {
+        if let Some(pos) = self.recency.iter().position(|s| s == statement) {
+            let key = self.recency.remove(pos);
+            self.recency.push(key);
+        }
+    }
+
+    fn get(&mut self, statement: &str) -> Option<ProofResult> {
+        let result = self.entries.get(statement).cloned();
+        if result.is_some() {
+            self.touch(statement);
+        }
+        result
+    }
+
+    fn insert(&mut self, statement: String, result: ProofResult) {
+        if self.entries.contains_key(&statement) {
+            self.touch(&statement);
+        } else {
+            if let Some(capacity) = self.capacity {
+                if capacity == 0 {
+                    return;
+                }
+                if self.recency.len() >= capacity {
+                    let evicted = self.recency.remove(0);
+                    self.entries.remove(&evicted);
+                }
+            }
+            self.recency.push(statement.clone());
+        }
+        self.entries.insert(statement, result);
+    }
+
+    /// Insert `result` only if `statement` isn't already cached, still
+    /// respecting the capacity limit. Used by `load_cache`, which merges
+    /// without overwriting.
+    fn insert_if_absent(&mut self, statement: String, result: ProofResult) {
+        if !self.entries.contains_key(&statement) {
+            self.insert(statement, result);
+        }
+    }
+
+    fn remove(&mut self, statement: &str) -> Option<ProofResult> {
+        self.recency.retain(|s| s != statement);
+        self.entries.remove(statement)
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+}
+
+/// A verification outcome cached by `ProofEngine`, returned by
+/// `remove_cached`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProofResult {
+    pub status: ProofStatus,
+    /// How long the prover took to produce `status`, in milliseconds.
+    /// Defaults to 0 so results cached before this field existed still
+    /// deserialize.
+    #[serde(default)]
+    pub duration_ms: u64,
+}
+
+/// One named prover's verdict within a `ConsensusResult`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProverVerdict {
+    pub prover: String,
+    pub status: ProofStatus,
+}
+
+/// The outcome of `verify_consensus`: either every registered prover
+/// agreed (`Unanimous`), or they didn't (`Conflicting`, listing each
+/// prover's own verdict so the caller can judge which to trust).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConsensusResult {
+    Unanimous(ProofStatus),
+    Conflicting(Vec<ProverVerdict>),
+}
+
+/// Normalize a statement so trivially-different renderings of the same
+/// logical statement (extra whitespace, different casing) share a cache
+/// slot and a `statement_id`: trims the ends, collapses internal
+/// whitespace runs to a single space, and lowercases everything.
+pub fn normalize_statement(s: &str) -> String {
+    s.trim().to_lowercase().split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Compute a short, stable, collision-resistant id for a statement: the
+/// first 12 hex characters of the SHA-256 hash of its normalized text.
+/// Normalizing (trimming, lower-casing, collapsing whitespace) means
+/// trivially-different renderings of the same statement share an id.
+pub fn statement_id(statement: &str) -> String {
+    let normalized = normalize_statement(statement);
+
+    let mut hasher = Sha256::new();
+    hasher.update(normalized.as_bytes());
+    let digest = hasher.finalize();
+
+    digest.iter().take(6).map(|b| format!("{b:02x}")).collect()
+}
+
+/// The templates every `ProofEngine` ships with: common proof shapes
+/// that don't need to be hand-written each time.
+fn default_templates() -> HashMap<String, String> {
+    let mut templates = HashMap::new();
+    templates.insert("reflexivity".to_string(), "{x} = {x}".to_string());
+    templates.insert("ordering".to_string(), "{a} <= {b} or {b} <= {a}".to_string());
+    templates
+}
+
+impl ProofEngine {
+    pub fn new() -> Self {
+        Self {
+            provers: Vec::new(),
+            default_prover: None,
+            statement_ids: HashMap::new(),
+            concurrency_limit: Arc::new(Semaphore::new(usize::MAX)),
+            availability_cache_ttl: None,
+            availability_cache: Mutex::new(HashMap::new()),
+            templates: default_templates(),
+            proof_cache: Mutex::new(ProofCache::new(None)),
+            retry_max_attempts: 1,
+            retry_base_delay: Duration::from_millis(0),
+        }
+    }
+}
+
+impl Default for ProofEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProofEngine {
+    /// Build a `ProofEngine` whose proof cache is bounded to at most `n`
+    /// entries, evicting the least-recently-used statement once full.
+    /// Both `get_cached_proof` and `verify_statement` count as a use that
+    /// refreshes a statement's recency, same as `verify_with_any_prover`.
+    pub fn with_cache_capacity(n: usize) -> Self {
+        Self {
+            proof_cache: Mutex::new(ProofCache::new(Some(n))),
+            ..Self::new()
+        }
+    }
+
+    /// Retry a prover invocation up to `max_attempts` times (so
+    /// `max_attempts: 1` is the no-retry default) when it comes back as
+    /// `ProofStatus::Error` - a transient I/O failure (e.g. a temp file
+    /// race), not a logical result. A genuine `Disproved`/`Undecidable`
+    /// verdict is final and is never retried. Each retry waits twice as
+    /// long as the last, starting from `base_delay`.
+    pub fn with_retry(mut self, max_attempts: usize, base_delay: Duration) -> Self {
+        self.retry_max_attempts = max_attempts.max(1);
+        self.retry_base_delay = base_delay;
+        self
+    }
+
+    /// Invoke `prover` on `statement`, retrying only `ProofStatus::Error`
+    /// results per `with_retry`'s configured policy.
+    fn verify_with_retries(&self, prover: &(dyn TheoremProver + Send + Sync), statement: &str) -> ProofStatus {
+        let mut delay = self.retry_base_delay;
+        for attempt in 0..self.retry_max_attempts {
+            let status = prover.verify(statement);
+            if !matches!(status, ProofStatus::Error(_)) || attempt + 1 == self.retry_max_attempts {
+                return status;
+            }
+            std::thread::sleep(delay);
+            delay *= 2;
+        }
+        unreachable!("retry_max_attempts is always at least 1")
+    }
+
+    /// Register a reusable statement template under `name`, with `{var}`
+    /// placeholders to be substituted by `verify_template`. Overwrites any
+    /// existing template of the same name, including the built-ins.
+    pub fn register_template(&mut self, name: &str, template: &str) {
+        self.templates.insert(name.to_string(), template.to_string());
+    }
+
+    /// Substitute `params` into the named template's `{var}` placeholders
+    /// and verify the resulting statement, exactly as `verify_statement`
+    /// would. Errors if the template doesn't exist or a placeholder has no
+    /// matching entry in `params`.
+    pub fn verify_template(
+        &mut self,
+        name: &str,
+        params: &HashMap<String, String>,
+        prover: Option<&str>,
+    ) -> Result<ProofStatus, ProofError> {
+        let template = self
+            .templates
+            .get(name)
+            .ok_or_else(|| ProofError::TranslationFailed(format!("no template named '{name}'")))?
+            .clone();
+
+        let mut statement = template;
+        for (var, value) in params {
+            statement = statement.replace(&format!("{{{var}}}"), value);
+        }
+        if statement.contains('{') && statement.contains('}') {
+            return Err(ProofError::TranslationFailed(format!(
+                "template '{name}' has unfilled placeholders: '{statement}'"
+            )));
+        }
+
+        self.register_statement(&statement);
+        self.verify_statement(&statement, prover)
+    }
+
+    /// Build a `ProofEngine` with an `ExternalProver` registered for each
+    /// known external tool found on `PATH` (currently "z3" and "lean"),
+    /// plus a `CoqProver` if `coqc` turns up at one of its usual install
+    /// locations (see `CoqProver::auto_detect`), skipping any that aren't
+    /// available. May register zero provers if none of the known tools
+    /// are installed - callers should handle `verify_with_any_prover`'s
+    /// "no provers registered" error gracefully rather than assuming at
+    /// least one exists.
+    pub fn with_auto_detected_provers() -> Self {
+        let mut engine = Self::new();
+        for (name, binary) in [("z3", "z3"), ("lean", "lean")] {
+            let prover = ExternalProver::new(name, binary);
+            if prover.is_available() {
+                engine.add_prover(Box::new(prover));
+            }
+        }
+        if let Some(coq) = CoqProver::auto_detect() {
+            engine.add_prover(Box::new(coq));
+        }
+        engine
+    }
+
+    /// Cache each prover's `is_available` result for `ttl`, only
+    /// re-checking once it goes stale. `None` disables caching, so every
+    /// call to `is_prover_available` re-checks the prover directly.
+    pub fn set_availability_cache_ttl(&mut self, ttl: Option<Duration>) {
+        self.availability_cache_ttl = ttl;
+        self.availability_cache.lock().unwrap().clear();
+    }
+
+    /// Whether the named prover is currently available, consulting (and
+    /// populating) the availability cache if a TTL is configured. Returns
+    /// `false` if no prover with that name is registered.
+    pub fn is_prover_available(&self, name: &str) -> bool {
+        let Some(prover) = self.provers.iter().find(|p| p.name() == name) else {
+            return false;
+        };
+
+        let Some(ttl) = self.availability_cache_ttl else {
+            return prover.is_available();
+        };
+
+        let mut cache = self.availability_cache.lock().unwrap();
+        if let Some((checked_at, available)) = cache.get(name) {
+            if checked_at.elapsed() < ttl {
+                return *available;
+            }
+        }
+
+        let available = prover.is_available();
+        cache.insert(name.to_string(), (Instant::now(), available));
+        available
+    }
+
+    /// Cap the number of prover subprocesses that may run concurrently
+    /// during batch/consensus operations to `n`.
+    pub fn set_max_concurrency(&mut self, n: usize) {
+        self.concurrency_limit = Arc::new(Semaphore::new(n.max(1)));
+    }
+
+    /// Run `f` for each statement, respecting the configured concurrency
+    /// limit: at most `max_concurrency` calls to `f` run at once.
+    fn run_limited<T, F>(&self, statements: &[String], f: F) -> Vec<T>
+    where
+        T: Send,
+        F: Fn(&str) -> T + Send + Sync,
+    {
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = statements
+                .iter()
+                .map(|statement| {
+                    scope.spawn(|| {
+                        self.concurrency_limit.acquire();
+                        let result = f(statement);
+                        self.concurrency_limit.release();
+                        result
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        })
+    }
+
+    pub fn add_prover(&mut self, prover: Box<dyn TheoremProver + Send + Sync>) {
+        self.provers.push(prover);
+    }
+
+    /// Set the preferred prover for `verify_statement(stmt, None)`. Errors
+    /// if no registered prover has that name.
+    pub fn set_default_prover(&mut self, name: &str) -> Result<(), ProofError> {
+        if !self.provers.iter().any(|p| p.name() == name) {
+            return Err(ProofError::ProverNotFound(name.to_string()));
+        }
+        self.default_prover = Some(name.to_string());
+        Ok(())
+    }
+
+    pub fn default_prover(&self) -> Option<&str> {
+        self.default_prover.as_deref()
+    }
+
+    /// Try every registered prover in order, returning the first result
+    /// that isn't an `Error`, `Timeout` or `Undecidable`. A `Timeout` from
+    /// one prover doesn't halt the fallback chain - we just move on to the
+    /// next prover. If no prover proves or disproves the statement, the
+    /// last status is returned.
+    pub fn verify_with_any_prover(&self, statement: &str) -> Result<ProofStatus, ProofError> {
+        if let Some(cached) = self.get_cached_proof(statement) {
+            return Ok(cached.status);
+        }
+
+        if self.provers.is_empty() {
+            return Err(ProofError::NoProversAvailable);
+        }
+
+        let mut last = None;
+        for prover in &self.provers {
+            let started = Instant::now();
+            let status = self.verify_with_retries(prover.as_ref(), statement);
+            let duration_ms = started.elapsed().as_millis() as u64;
+            match status {
+                ProofStatus::Proved | ProofStatus::Disproved => {
+                    self.cache_result(statement, status.clone(), duration_ms);
+                    return Ok(status);
+                }
+                ProofStatus::Timeout | ProofStatus::Undecidable(_) | ProofStatus::Error(_) => {
+                    last = Some((status, duration_ms))
+                }
+            }
+        }
+        let (status, duration_ms) = last.expect("provers is non-empty");
+        self.cache_result(statement, status.clone(), duration_ms);
+        Ok(status)
+    }
+
+    /// Verify every statement in `statements` via `verify_with_any_prover`,
+    /// paired with the statement it came from. Cached statements short-
+    /// circuit exactly as a single `verify_with_any_prover` call would -
+    /// this is just a convenience for driving many statements through the
+    /// cache at once, e.g. from a `prove-file` CLI command. Runs the
+    /// uncached statements concurrently, bounded by `set_max_concurrency`.
+    pub fn verify_batch(&mut self, statements: &[&str]) -> Vec<(String, Result<ProofResult, ProofError>)> {
+        let owned: Vec<String> = statements.iter().map(|s| s.to_string()).collect();
+        let engine: &Self = self;
+        let results = engine.run_limited(&owned, |statement| {
+            engine
+                .verify_with_any_prover(statement)
+                .map(|_| engine.get_cached_proof(statement).expect("verify_with_any_prover caches its result"))
+        });
+        owned.into_iter().zip(results).collect()
+    }
+
+    /// Run every registered prover concurrently (one thread each, bounded
+    /// by `set_max_concurrency`) and report whether they agree. Only a
+    /// unanimous result is cached, since a disputed one isn't safe to hand
+    /// back from `verify_with_any_prover` without the caller seeing the
+    /// disagreement.
+    pub fn verify_consensus(&mut self, statement: &str) -> Result<ConsensusResult, ProofError> {
+        if self.provers.is_empty() {
+            return Err(ProofError::NoProversAvailable);
+        }
+
+        let semaphore = &self.concurrency_limit;
+        let verdicts: Vec<ProverVerdict> = std::thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .provers
+                .iter()
+                .map(|prover| {
+                    scope.spawn(move || {
+                        semaphore.acquire();
+                        let verdict = ProverVerdict {
+                            prover: prover.name().to_string(),
+                            status: prover.verify(statement),
+                        };
+                        semaphore.release();
+                        verdict
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        let first_status = verdicts[0].status.clone();
+        if verdicts.iter().all(|v| v.status == first_status) {
+            self.cache_result(statement, first_status.clone(), 0);
+            Ok(ConsensusResult::Unanimous(first_status))
+        } else {
+            Ok(ConsensusResult::Conflicting(verdicts))
+        }
+    }
+
+    /// Cache `status` under `statement`'s normalized form, so trivially-
+    /// different renderings of the same statement share a cache slot - see
+    /// `normalize_statement`.
+    fn cache_result(&self, statement: &str, status: ProofStatus, duration_ms: u64) {
+        self.proof_cache
+            .lock()
+            .unwrap()
+            .insert(normalize_statement(statement), ProofResult { status, duration_ms });
+    }
+
+    /// Look up `statement` (by its normalized form) in the proof cache
+    /// without invoking any prover, refreshing its recency on a hit
+    /// exactly like `verify_with_any_prover`/`verify_statement` do.
+    pub fn get_cached_proof(&self, statement: &str) -> Option<ProofResult> {
+        self.proof_cache.lock().unwrap().get(&normalize_statement(statement))
+    }
+
+    /// Drop every cached `verify_with_any_prover` result, forcing the next
+    /// call for any statement to re-invoke the provers.
+    pub fn clear_cache(&mut self) {
+        self.proof_cache.lock().unwrap().clear();
+    }
+
+    /// Remove and return the cached result for `statement`, if any, so
+    /// the next `verify_with_any_prover` call for it re-invokes the
+    /// provers instead of reusing a stale result.
+    pub fn remove_cached(&mut self, statement: &str) -> Option<ProofResult> {
+        self.proof_cache.lock().unwrap().remove(&normalize_statement(statement))
+    }
+
+    /// Write the statement-result cache to `path` as JSON, so the next
+    /// session can load it with `load_cache` instead of re-proving
+    /// everything from scratch.
+    pub fn save_cache(&self, path: &Path) -> Result<(), ProofError> {
+        let cache = self.proof_cache.lock().unwrap();
+        let json = serde_json::to_string_pretty(&cache.entries)
+            .map_err(|e| ProofError::ExecutionFailed(format!("failed to serialize proof cache: {e}")))?;
+        fs::write(path, json)
+            .map_err(|e| ProofError::ExecutionFailed(format!("failed to write '{}': {e}", path.display())))
+    }
+
+    /// Merge the cache previously saved with `save_cache` at `path` into
+    /// the current cache, without discarding entries already present.
+    /// Malformed entries in the file are skipped rather than failing the
+    /// whole load.
+    pub fn load_cache(&mut self, path: &Path) -> Result<(), ProofError> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| ProofError::ExecutionFailed(format!("failed to read '{}': {e}", path.display())))?;
+        let loaded: HashMap<String, serde_json::Value> = serde_json::from_str(&contents).map_err(|e| {
+            ProofError::ExecutionFailed(format!("invalid proof cache JSON in '{}': {e}", path.display()))
+        })?;
+
+        let mut cache = self.proof_cache.lock().unwrap();
+        for (statement, value) in loaded {
+            if let Ok(result) = serde_json::from_value::<ProofResult>(value) {
+                cache.insert_if_absent(statement, result);
+            }
+        }
+        Ok(())
+    }
+
+    /// Try `statement` against only the named provers, in order, returning
+    /// the first result that isn't an `Error`, `Timeout` or `Undecidable` - same
+    /// fallback behavior as `verify_with_any_prover`, but scoped to a
+    /// caller-chosen subset instead of every registered prover.
+    pub fn verify_with_provers(&self, statement: &str, prover_names: &[&str]) -> Result<ProofStatus, ProofError> {
+        if prover_names.is_empty() {
+            return Err(ProofError::ExecutionFailed("prover_names must not be empty".to_string()));
+        }
+
+        let mut last = None;
+        for &name in prover_names {
+            let prover = self
+                .provers
+                .iter()
+                .find(|p| p.name() == name)
+                .ok_or_else(|| ProofError::ProverNotFound(name.to_string()))?;
+            let status = self.verify_with_retries(prover.as_ref(), statement);
+            match status {
+                ProofStatus::Proved | ProofStatus::Disproved => return Ok(status),
+                ProofStatus::Timeout | ProofStatus::Undecidable(_) | ProofStatus::Error(_) => last = Some(status),
+            }
+        }
+        Ok(last.expect("prover_names is non-empty"))
+    }
+
+    /// Verify `statement` with the named prover, or - if `prover_name` is
+    /// `None` - the configured default prover if it's still registered,
+    /// falling back to the first available prover otherwise.
+    pub fn verify_statement(
+        &self,
+        statement: &str,
+        prover_name: Option<&str>,
+    ) -> Result<ProofStatus, ProofError> {
+        if let Some(cached) = self.get_cached_proof(statement) {
+            return Ok(cached.status);
+        }
+
+        let chosen = match prover_name {
+            Some(name) => name,
+            None => self
+                .default_prover
+                .as_deref()
+                .filter(|name| self.provers.iter().any(|p| p.name() == *name))
+                .unwrap_or_else(|| self.provers.first().map(|p| p.name()).unwrap_or_default()),
+        };
+
+        let prover = self
+            .provers
+            .iter()
+            .find(|p| p.name() == chosen)
+            .ok_or_else(|| ProofError::ProverNotFound(chosen.to_string()))?;
+        let started = Instant::now();
+        let status = self.verify_with_retries(prover.as_ref(), statement);
+        self.cache_result(statement, status.clone(), started.elapsed().as_millis() as u64);
+        Ok(status)
+    }
+
+    /// Register a statement, returning (and remembering) its canonical id.
+    pub fn register_statement(&mut self, statement: &str) -> String {
+        let id = statement_id(statement);
+        self.statement_ids.entry(id.clone()).or_insert_with(|| statement.to_string());
+        id
+    }
+
+    /// Recover the original statement text for a previously registered id.
+    pub fn statement_for_id(&self, id: &str) -> Option<&str> {
+        self.statement_ids.get(id).map(|s| s.as_str())
+    }
+
+    /// Record that `statement` was checked with the given `status`: this
+    /// registers the statement, adds a proof node for it to `graph`, and
+    /// links that node to every existing concept whose key or content
+    /// appears in the statement text via a "uses_concept" relation, so the
+    /// proof's dependencies are queryable through the graph. Returns the
+    /// proof node's key.
+    pub fn record_proof(&mut self, graph: &mut MemoryGraph, statement: &str, status: ProofStatus) -> String {
+        let id = self.register_statement(statement);
+        let proof_key = format!("proof:{id}");
+
+        let confidence = match status {
+            ProofStatus::Proved => 1.0,
+            ProofStatus::Disproved => 0.0,
+            ProofStatus::Timeout | ProofStatus::Undecidable(_) | ProofStatus::Error(_) => 0.5,
+        };
+
+        graph.add_concept(SymbolicNode {
+            key: proof_key.clone(),
+            content: statement.to_string(),
+            confidence,
+            framework: "proof".to_string(),
+            last_updated: Utc::now(),
+            provenance: None,
+            metadata: HashMap::new(),
+        });
+
+        let statement_lower = statement.to_lowercase();
+        let referenced_keys: Vec<String> = graph
+            .concepts
+            .values()
+            .filter(|node| node.key != proof_key)
+            .filter(|node| {
+                statement_lower.contains(&node.key.to_lowercase())
+                    || statement_lower.contains(&node.content.to_lowercase())
+            })
+            .map(|node| node.key.clone())
+            .collect();
+
+        for concept_key in referenced_keys {
+            graph
+                .add_relationship(SymbolicRelation {
+                    from: proof_key.clone(),
+                    to: concept_key,
+                    relation_type: "uses_concept".to_string(),
+                    strength: 1.0,
+                    last_updated: Utc::now(),
+                })
+                .expect("proof_key and concept_key both name existing, distinct concepts");
+        }
+
+        proof_key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proof_status_round_trips_through_display_and_from_str() {
+        for status in [
+            ProofStatus::Proved,
+            ProofStatus::Disproved,
+            ProofStatus::Timeout,
+            ProofStatus::Undecidable(String::new()),
+            ProofStatus::Error(String::new()),
+        ] {
+            let parsed: ProofStatus = status.to_string().parse().expect("valid status string");
+            assert_eq!(parsed, status);
+        }
+    }
+
+    #[test]
+    fn proof_status_from_str_is_case_insensitive_and_rejects_unknown() {
+        assert_eq!("PROVEN".parse::<ProofStatus>(), Ok(ProofStatus::Proved));
+        assert!("InProgress".parse::<ProofStatus>().is_err());
+        assert!("not a status".parse::<ProofStatus>().is_err());
+    }
+
+    #[test]
+    fn classify_lean_output_distinguishes_unsolved_goals_from_syntax_errors() {
+        let unsolved = classify_lean_output(
+            false,
+            "example : 1 + 1 = 3 := by decide\nerror: unsolved goals\n⊢ 1 + 1 = 3",
+        );
+        assert_eq!(unsolved, ProofStatus::Disproved);
+
+        let syntax_error = classify_lean_output(
+            false,
+            "foo.lean:3:2: error: unknown identifier 'theorm'",
+        );
+        assert!(matches!(syntax_error, ProofStatus::Error(_)));
+    }
+
+    #[test]
+    fn statement_id_is_stable_across_runs() {
+        let statement = "forall n, n + 0 = n";
+        let first = statement_id(statement);
+        let second = statement_id(statement);
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 12);
+    }
+
+    #[test]
+    fn normalize_statement_collapses_whitespace_and_casing() {
+        assert_eq!(
+            normalize_statement(" Forall  X.  X = X "),
+            normalize_statement("forall x. x = x")
+        );
+        assert_eq!(normalize_statement("forall x. x = x"), "forall x. x = x");
+    }
+
+    #[test]
+    fn statement_id_is_the_same_for_whitespace_and_casing_variants() {
+        assert_eq!(statement_id("forall x. x = x"), statement_id(" forall  X.  x = x "));
+    }
+
+    #[test]
+    fn whitespace_and_casing_variants_of_a_statement_share_a_cache_entry() {
+        let verify_calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut engine = ProofEngine::new();
+        engine.add_prover(Box::new(CountingVerifyProver {
+            status: ProofStatus::Proved,
+            verify_calls: verify_calls.clone(),
+        }));
+
+        engine.verify_with_any_prover("forall x. x = x").unwrap();
+        engine.verify_with_any_prover(" forall  X.  x = x ").unwrap();
+        assert_eq!(verify_calls.load(std::sync::atomic::Ordering::SeqCst), 1, "second variant should hit the cache");
+
+        let cached = engine
+            .get_cached_proof("FORALL X. X = X")
+            .expect("normalized lookup should find the same entry");
+        assert_eq!(cached.status, ProofStatus::Proved);
+    }
+
+    #[test]
+    fn reverse_lookup_finds_the_original_statement() {
+        let mut engine = ProofEngine::new();
+        let statement = "forall n, n + 0 = n";
+        let id = engine.register_statement(statement);
+        assert_eq!(engine.statement_for_id(&id), Some(statement));
+    }
+
+    #[test]
+    fn unknown_id_returns_none() {
+        let engine = ProofEngine::new();
+        assert_eq!(engine.statement_for_id("deadbeefcafe"), None);
+    }
+
+    struct FixedProver {
+        name: String,
+        status: ProofStatus,
+    }
+
+    impl TheoremProver for FixedProver {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn verify(&self, _statement: &str) -> ProofStatus {
+            self.status.clone()
+        }
+    }
+
+    #[test]
+    fn default_prover_is_honored_when_available() {
+        let mut engine = ProofEngine::new();
+        engine.add_prover(Box::new(FixedProver {
+            name: "z3".to_string(),
+            status: ProofStatus::Proved,
+        }));
+        engine.add_prover(Box::new(FixedProver {
+            name: "lean".to_string(),
+            status: ProofStatus::Disproved,
+        }));
+
+        engine.set_default_prover("lean").expect("lean is registered");
+        assert_eq!(engine.default_prover(), Some("lean"));
+
+        let status = engine.verify_statement("1 = 1", None).expect("prover found");
+        assert_eq!(status, ProofStatus::Disproved);
+    }
+
+    #[test]
+    fn set_default_prover_rejects_unknown_name() {
+        let mut engine = ProofEngine::new();
+        assert!(engine.set_default_prover("nope").is_err());
+    }
+
+    #[test]
+    fn max_concurrency_of_one_serializes_work() {
+        use std::time::{Duration, Instant};
+
+        let mut engine = ProofEngine::new();
+        engine.set_max_concurrency(1);
+
+        let statements: Vec<String> = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let per_task = Duration::from_millis(50);
+
+        let start = Instant::now();
+        let _: Vec<()> = engine.run_limited(&statements, move |_s| {
+            std::thread::sleep(per_task);
+        });
+        let elapsed = start.elapsed();
+
+        // With concurrency 1, total wall time should be roughly the sum of
+        // the individual sleeps, not the max - i.e. serialized, not
+        // parallel.
+        assert!(
+            elapsed >= per_task * 2,
+            "expected serialized execution, took {elapsed:?}"
+        );
+    }
+
+    #[test]
+    fn mock_prover_always_returns_its_configured_status() {
+        let mut engine = ProofEngine::new();
+        engine.add_prover(Box::new(MockProver::new("mock", ProofStatus::Proved)));
+
+        let status = engine
+            .verify_statement("anything", Some("mock"))
+            .expect("mock is registered");
+        assert_eq!(status, ProofStatus::Proved);
+    }
+
+    #[test]
+    fn verify_with_provers_only_tries_the_given_list() {
+        let mut engine = ProofEngine::new();
+        engine.add_prover(Box::new(FixedProver {
+            name: "z3".to_string(),
+            status: ProofStatus::Proved,
+        }));
+        engine.add_prover(Box::new(FixedProver {
+            name: "lean".to_string(),
+            status: ProofStatus::Disproved,
+        }));
+
+        let status = engine
+            .verify_with_provers("1 = 1", &["lean"])
+            .expect("lean is registered");
+        assert_eq!(status, ProofStatus::Disproved);
+    }
+
+    #[test]
+    fn verify_with_provers_errors_on_unknown_prover() {
+        let engine = ProofEngine::new();
+        assert!(engine.verify_with_provers("1 = 1", &["nope"]).is_err());
+    }
+
+    #[test]
+    fn timeout_does_not_halt_the_fallback_chain() {
+        let mut engine = ProofEngine::new();
+        engine.add_prover(Box::new(FixedProver {
+            name: "always-times-out".to_string(),
+            status: ProofStatus::Timeout,
+        }));
+        engine.add_prover(Box::new(FixedProver {
+            name: "z3".to_string(),
+            status: ProofStatus::Proved,
+        }));
+
+        let status = engine
+            .verify_with_any_prover("1 = 1")
+            .expect("a non-timing-out prover is registered");
+        assert_eq!(status, ProofStatus::Proved);
+        assert_ne!(status, ProofStatus::Timeout);
+    }
+
+    #[test]
+    fn verify_consensus_reports_unanimous_agreement_and_caches_it() {
+        let mut engine = ProofEngine::new();
+        engine.add_prover(Box::new(FixedProver {
+            name: "z3".to_string(),
+            status: ProofStatus::Proved,
+        }));
+        engine.add_prover(Box::new(FixedProver {
+            name: "lean".to_string(),
+            status: ProofStatus::Proved,
+        }));
+
+        let statement = "1 = 1";
+        let result = engine.verify_consensus(statement).expect("provers are registered");
+        assert_eq!(result, ConsensusResult::Unanimous(ProofStatus::Proved));
+
+        let cached = engine.remove_cached(statement).expect("unanimous result is cached");
+        assert_eq!(cached.status, ProofStatus::Proved);
+    }
+
+    #[test]
+    fn verify_consensus_reports_conflicting_verdicts_and_does_not_cache() {
+        let mut engine = ProofEngine::new();
+        engine.add_prover(Box::new(FixedProver {
+            name: "z3".to_string(),
+            status: ProofStatus::Proved,
+        }));
+        engine.add_prover(Box::new(FixedProver {
+            name: "lean".to_string(),
+            status: ProofStatus::Disproved,
+        }));
+
+        let statement = "1 = 2";
+        let result = engine.verify_consensus(statement).expect("provers are registered");
+        match result {
+            ConsensusResult::Conflicting(verdicts) => {
+                assert_eq!(verdicts.len(), 2);
+                assert!(verdicts.iter().any(|v| v.prover == "z3" && v.status == ProofStatus::Proved));
+                assert!(verdicts.iter().any(|v| v.prover == "lean" && v.status == ProofStatus::Disproved));
+            }
+            ConsensusResult::Unanimous(_) => panic!("expected a conflicting result"),
+        }
+
+        assert!(engine.remove_cached(statement).is_none(), "conflicting results should not be cached");
+    }
+
+    #[test]
+    fn falls_back_to_first_available_when_no_default_set() {
+        let mut engine = ProofEngine::new();
+        engine.add_prover(Box::new(FixedProver {
+            name: "z3".to_string(),
+            status: ProofStatus::Proved,
+        }));
+
+        let status = engine.verify_statement("1 = 1", None).expect("prover found");
+        assert_eq!(status, ProofStatus::Proved);
+    }
+
+    #[test]
+    fn record_proof_links_the_proof_node_to_referenced_concepts() {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(SymbolicNode {
+            key: "peano-axioms".to_string(),
+            content: "the Peano axioms define the natural numbers".to_string(),
+            confidence: 0.9,
+            framework: "mathematics".to_string(),
+            last_updated: Utc::now(),
+            provenance: None,
+            metadata: HashMap::new(),
+        });
+
+        let mut engine = ProofEngine::new();
+        let proof_key = engine.record_proof(&mut graph, "forall n, n + 0 = n by peano-axioms", ProofStatus::Proved);
+
+        let proof_node = graph.get_concept(&proof_key).expect("proof node was added");
+        assert_eq!(proof_node.content, "forall n, n + 0 = n by peano-axioms");
+        assert_eq!(proof_node.confidence, 1.0);
+
+        let uses_concept = graph
+            .relationships
+            .iter()
+            .find(|r| r.from == proof_key && r.relation_type == "uses_concept");
+        assert!(uses_concept.is_some());
+        assert_eq!(uses_concept.unwrap().to, "peano-axioms");
+    }
+
+    struct CountingVerifyProver {
+        status: ProofStatus,
+        verify_calls: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl TheoremProver for CountingVerifyProver {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        fn verify(&self, _statement: &str) -> ProofStatus {
+            self.verify_calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.status.clone()
+        }
+    }
+
+    #[test]
+    fn save_cache_and_load_cache_round_trip_into_a_fresh_engine() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("proof_cache.json");
+
+        let mut engine = ProofEngine::new();
+        engine.add_prover(Box::new(MockProver::new("mock", ProofStatus::Proved)));
+        engine.verify_with_any_prover("forall n, n + 0 = n").unwrap();
+        engine.save_cache(&path).expect("save succeeds");
+
+        let mut fresh = ProofEngine::new();
+        fresh.load_cache(&path).expect("load succeeds");
+        let restored = fresh.remove_cached("forall n, n + 0 = n").expect("entry was loaded");
+        assert_eq!(restored.status, ProofStatus::Proved);
+    }
+
+    #[test]
+    fn load_cache_merges_without_overwriting_existing_entries() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("proof_cache.json");
+        fs::write(
+            &path,
+            r#"{"forall n, n + 0 = n": {"status": "Proved"}}"#,
+        )
+        .expect("writes fixture");
+
+        let mut engine = ProofEngine::new();
+        engine.add_prover(Box::new(MockProver::new("mock", ProofStatus::Disproved)));
+        engine.verify_with_any_prover("forall n, n + 0 = n").unwrap();
+
+        engine.load_cache(&path).expect("load succeeds");
+        let restored = engine.remove_cached("forall n, n + 0 = n").expect("entry still present");
+        assert_eq!(restored.status, ProofStatus::Disproved);
+    }
+
+    #[test]
+    fn verify_with_any_prover_caches_and_remove_cached_forces_a_retry() {
+        let verify_calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut engine = ProofEngine::new();
+        engine.add_prover(Box::new(CountingVerifyProver {
+            status: ProofStatus::Proved,
+            verify_calls: verify_calls.clone(),
+        }));
+
+        let statement = "forall n, n + 0 = n";
+        assert_eq!(engine.verify_with_any_prover(statement), Ok(ProofStatus::Proved));
+        assert_eq!(engine.verify_with_any_prover(statement), Ok(ProofStatus::Proved));
+        assert_eq!(verify_calls.load(std::sync::atomic::Ordering::SeqCst), 1, "second call should hit the cache");
+
+        let removed = engine.remove_cached(statement).expect("entry was cached");
+        assert_eq!(removed.status, ProofStatus::Proved);
+
+        assert_eq!(engine.verify_with_any_prover(statement), Ok(ProofStatus::Proved));
+        assert_eq!(verify_calls.load(std::sync::atomic::Ordering::SeqCst), 2, "removing the cache entry should force a re-verify");
+    }
+
+    #[test]
+    fn verify_batch_reuses_the_cache_for_already_verified_statements() {
+        let verify_calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut engine = ProofEngine::new();
+        engine.add_prover(Box::new(CountingVerifyProver {
+            status: ProofStatus::Proved,
+            verify_calls: verify_calls.clone(),
+        }));
+
+        let cached_statement = "forall n, n + 0 = n";
+        engine.verify_with_any_prover(cached_statement).expect("primes the cache");
+        assert_eq!(verify_calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        let results = engine.verify_batch(&[cached_statement, "1 = 1", "2 = 2"]);
+
+        assert_eq!(results.len(), 3);
+        for (statement, result) in &results {
+            let result = result.as_ref().unwrap_or_else(|e| panic!("{statement} failed: {e}"));
+            assert_eq!(result.status, ProofStatus::Proved);
+        }
+        assert_eq!(
+            verify_calls.load(std::sync::atomic::Ordering::SeqCst),
+            3,
+            "the cached statement should not re-invoke the prover, only the two fresh ones should"
+        );
+    }
+
+    #[test]
+    fn verify_with_any_prover_caches_the_duration_the_prover_took() {
+        struct SlowMockProver {
+            status: ProofStatus,
+            sleep_ms: u64,
+        }
+
+        impl TheoremProver for SlowMockProver {
+            fn name(&self) -> &str {
+                "slow-mock"
+            }
+
+            fn verify(&self, _statement: &str) -> ProofStatus {
+                std::thread::sleep(Duration::from_millis(self.sleep_ms));
+                self.status.clone()
+            }
+        }
+
+        let mut engine = ProofEngine::new();
+        engine.add_prover(Box::new(SlowMockProver {
+            status: ProofStatus::Proved,
+            sleep_ms: 20,
+        }));
+
+        let statement = "forall n, n + 0 = n";
+        engine.verify_with_any_prover(statement).expect("mock prover succeeds");
+
+        let cached = engine.remove_cached(statement).expect("result was cached");
+        assert_eq!(cached.status, ProofStatus::Proved);
+        assert!(cached.duration_ms >= 20, "expected duration_ms >= 20, got {}", cached.duration_ms);
+    }
+
+    /// A prover that fails with `ProofStatus::Error` on its first
+    /// `fail_count` calls, then reports `Proved` on every call after that.
+    struct FlakyProver {
+        fail_count: usize,
+        calls: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl TheoremProver for FlakyProver {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        fn verify(&self, _statement: &str) -> ProofStatus {
+            let call = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if call < self.fail_count {
+                ProofStatus::Error("transient temp file race".to_string())
+            } else {
+                ProofStatus::Proved
+            }
+        }
+    }
+
+    #[test]
+    fn with_retry_recovers_from_transient_errors_before_giving_up() {
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut engine = ProofEngine::new().with_retry(3, Duration::from_millis(1));
+        engine.add_prover(Box::new(FlakyProver {
+            fail_count: 2,
+            calls: calls.clone(),
+        }));
+
+        let status = engine.verify_with_any_prover("1 = 1").expect("succeeds on the third attempt");
+        assert_eq!(status, ProofStatus::Proved);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn with_retry_gives_up_after_max_attempts_and_reports_the_last_error() {
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut engine = ProofEngine::new().with_retry(2, Duration::from_millis(1));
+        engine.add_prover(Box::new(FlakyProver {
+            fail_count: 10,
+            calls: calls.clone(),
+        }));
+
+        let status = engine.verify_with_any_prover("1 = 1").expect("falls through to the final Error status");
+        assert!(matches!(status, ProofStatus::Error(_)));
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn with_retry_does_not_retry_a_final_disproved_verdict() {
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut engine = ProofEngine::new().with_retry(5, Duration::from_millis(1));
+        engine.add_prover(Box::new(CountingVerifyProver {
+            status: ProofStatus::Disproved,
+            verify_calls: calls.clone(),
+        }));
+
+        let status = engine.verify_with_any_prover("1 = 2").expect("mock prover succeeds");
+        assert_eq!(status, ProofStatus::Disproved);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1, "a final verdict should not be retried");
+    }
+
+    #[test]
+    fn clear_cache_forces_a_retry_for_every_statement() {
+        let verify_calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut engine = ProofEngine::new();
+        engine.add_prover(Box::new(CountingVerifyProver {
+            status: ProofStatus::Proved,
+            verify_calls: verify_calls.clone(),
+        }));
+
+        engine.verify_with_any_prover("a").unwrap();
+        engine.verify_with_any_prover("b").unwrap();
+        assert_eq!(verify_calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        engine.clear_cache();
+        engine.verify_with_any_prover("a").unwrap();
+        engine.verify_with_any_prover("b").unwrap();
+        assert_eq!(verify_calls.load(std::sync::atomic::Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn cache_capacity_evicts_the_least_recently_used_statement() {
+        let verify_calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut engine = ProofEngine::with_cache_capacity(2);
+        engine.add_prover(Box::new(CountingVerifyProver {
+            status: ProofStatus::Proved,
+            verify_calls: verify_calls.clone(),
+        }));
+
+        engine.verify_with_any_prover("a").unwrap();
+        engine.verify_with_any_prover("b").unwrap();
+        // Touch "a" via get_cached_proof so "b" becomes the least-recently-used.
+        engine.get_cached_proof("a").expect("a is cached");
+        // Inserting a third statement should evict "b", not "a".
+        engine.verify_with_any_prover("c").unwrap();
+        assert_eq!(verify_calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+
+        assert!(engine.get_cached_proof("a").is_some(), "a was recently used, should survive");
+        assert!(engine.get_cached_proof("b").is_none(), "b was least-recently-used, should be evicted");
+        assert!(engine.get_cached_proof("c").is_some());
+
+        // "b" is no longer cached, so re-verifying it invokes the prover again.
+        engine.verify_with_any_prover("b").unwrap();
+        assert_eq!(verify_calls.load(std::sync::atomic::Ordering::SeqCst), 4);
+    }
+
+    struct CountingAvailabilityProver {
+        name: String,
+        available: bool,
+        availability_checks: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl TheoremProver for CountingAvailabilityProver {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn verify(&self, _statement: &str) -> ProofStatus {
+            ProofStatus::Proved
+        }
+
+        fn is_available(&self) -> bool {
+            self.availability_checks.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.available
+        }
+    }
+
+    #[test]
+    fn availability_cache_avoids_rechecking_within_the_ttl() {
+        let availability_checks = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut engine = ProofEngine::new();
+        engine.add_prover(Box::new(CountingAvailabilityProver {
+            name: "z3".to_string(),
+            available: true,
+            availability_checks: availability_checks.clone(),
+        }));
+        engine.set_availability_cache_ttl(Some(Duration::from_secs(60)));
+
+        for _ in 0..5 {
+            assert!(engine.is_prover_available("z3"));
+        }
+
+        assert_eq!(availability_checks.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn no_ttl_rechecks_every_call() {
+        let availability_checks = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut engine = ProofEngine::new();
+        engine.add_prover(Box::new(CountingAvailabilityProver {
+            name: "z3".to_string(),
+            available: true,
+            availability_checks: availability_checks.clone(),
+        }));
+
+        for _ in 0..3 {
+            engine.is_prover_available("z3");
+        }
+
+        assert_eq!(availability_checks.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn verify_template_substitutes_params_and_caches_the_statement() {
+        let mut engine = ProofEngine::new();
+        engine.add_prover(Box::new(MockProver::new("mock", ProofStatus::Proved)));
+        engine.register_template("commutes", "{a} + {b} = {b} + {a}");
+
+        let mut params = HashMap::new();
+        params.insert("a".to_string(), "2".to_string());
+        params.insert("b".to_string(), "3".to_string());
+
+        let status = engine
+            .verify_template("commutes", &params, Some("mock"))
+            .expect("template and prover both exist");
+        assert_eq!(status, ProofStatus::Proved);
+
+        let id = statement_id("2 + 3 = 3 + 2");
+        assert_eq!(engine.statement_for_id(&id), Some("2 + 3 = 3 + 2"));
+    }
+
+    #[test]
+    fn verify_template_errors_on_unknown_template() {
+        let mut engine = ProofEngine::new();
+        assert!(engine.verify_template("does-not-exist", &HashMap::new(), None).is_err());
+    }
+
+    #[test]
+    fn built_in_reflexivity_template_is_available_by_default() {
+        let mut engine = ProofEngine::new();
+        engine.add_prover(Box::new(MockProver::new("mock", ProofStatus::Proved)));
+
+        let mut params = HashMap::new();
+        params.insert("x".to_string(), "n".to_string());
+        let status = engine
+            .verify_template("reflexivity", &params, Some("mock"))
+            .expect("built-in template exists");
+        assert_eq!(status, ProofStatus::Proved);
+    }
+
+    #[test]
+    fn auto_detected_provers_gracefully_handles_none_installed() {
+        // In this sandbox neither "z3" nor "lean" is on PATH, so this
+        // should register zero provers rather than erroring.
+        let engine = ProofEngine::with_auto_detected_provers();
+        match engine.verify_with_any_prover("1 = 1") {
+            Ok(_) => (),
+            Err(e) => assert_eq!(e, ProofError::NoProversAvailable),
+        }
+    }
+
+    #[test]
+    fn verify_with_any_prover_reports_no_provers_available() {
+        let engine = ProofEngine::new();
+        assert_eq!(engine.verify_with_any_prover("1 = 1"), Err(ProofError::NoProversAvailable));
+    }
+
+    #[test]
+    fn verify_statement_reports_prover_not_found() {
+        let engine = ProofEngine::new();
+        assert_eq!(
+            engine.verify_statement("1 = 1", Some("nonexistent")),
+            Err(ProofError::ProverNotFound("nonexistent".to_string()))
+        );
+    }
+
+    /// Write a shell script to `dir` that sleeps for `sleep_secs` before
+    /// exiting successfully, and return its path with the executable bit set.
+    fn write_sleeping_script(dir: &std::path::Path, sleep_secs: u64) -> std::path::PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join("slow-prover.sh");
+        fs::write(&path, format!("#!/bin/sh\nsleep {sleep_secs}\nexit 0\n")).expect("write mock prover script");
+        fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).expect("make script executable");
+        path
+    }
+
+    #[test]
+    fn external_prover_reports_timeout_when_it_runs_past_its_timeout() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let script = write_sleeping_script(dir.path(), 5);
+
+        let prover = ExternalProver::new("slow", script.to_str().expect("utf8 path"))
+            .with_timeout(Duration::from_millis(100));
+        let status = prover.verify("anything");
+
+        assert_eq!(status, ProofStatus::Timeout);
+    }
+
+    #[test]
+    fn external_prover_completes_normally_within_its_timeout() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let script = write_sleeping_script(dir.path(), 0);
+
+        let prover = ExternalProver::new("fast", script.to_str().expect("utf8 path"))
+            .with_timeout(Duration::from_secs(5));
+        let status = prover.verify("anything");
+
+        assert_eq!(status, ProofStatus::Proved);
+    }
+
+    #[test]
+    fn extra_args_are_passed_through_in_order_ahead_of_the_statement() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let script = dir.path().join("echo-args.sh");
+        let output_file = dir.path().join("args.txt");
+        fs::write(
+            &script,
+            format!("#!/bin/sh\nprintf '%s\\n' \"$@\" > {:?}\nexit 0\n", output_file),
+        )
+        .expect("write mock prover script");
+        fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).expect("make script executable");
+
+        let prover = ExternalProver::new("mock-ext", script.to_str().expect("utf8 path"))
+            .with_extra_args(vec!["-smt2".to_string(), "--verbose".to_string()]);
+        let status = prover.verify("1 = 1");
+        assert_eq!(status, ProofStatus::Proved);
+
+        let contents = fs::read_to_string(&output_file).expect("script wrote its args");
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines, vec!["-smt2", "--verbose", "1 = 1"]);
+    }
+
+    /// Write a mock `coqc` to `dir` that exits with `exit_code` and, on
+    /// failure, emits `stderr`. Returns the script's path with the
+    /// executable bit set.
+    fn write_mock_coqc(dir: &std::path::Path, exit_code: i32, stderr: &str) -> std::path::PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join("coqc.sh");
+        fs::write(
+            &path,
+            format!("#!/bin/sh\nprintf '%s' {:?} 1>&2\nexit {exit_code}\n", stderr),
+        )
+        .expect("write mock coqc script");
+        fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).expect("make script executable");
+        path
+    }
+
+    #[test]
+    fn coq_prover_reports_proved_on_success_and_writes_a_v_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        // Succeeds only when handed a `.v` file that exists.
+        let script = dir.path().join("coqc.sh");
+        fs::write(&script, "#!/bin/sh\ncase \"$1\" in *.v) [ -f \"$1\" ] && exit 0 ;; esac\nexit 1\n")
+            .expect("write mock coqc script");
+        fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).expect("make script executable");
+
+        let prover = CoqProver::new(script.to_str().expect("utf8 path"));
+        let status = prover.verify("1 = 1");
+
+        assert_eq!(status, ProofStatus::Proved);
+    }
+
+    #[test]
+    fn coq_prover_distinguishes_unable_to_unify_from_other_errors() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let script = write_mock_coqc(dir.path(), 1, "Error: Unable to unify \"1\" with \"2\".");
+
+        let prover = CoqProver::new(script.to_str().expect("utf8 path"));
+        let status = prover.verify("1 = 2");
+
+        assert_eq!(status, ProofStatus::Disproved);
+    }
+
+    #[test]
+    fn coq_prover_reports_error_for_a_syntax_error() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let script = write_mock_coqc(dir.path(), 1, "Syntax error: illegal begin of vernac.");
+
+        let prover = CoqProver::new(script.to_str().expect("utf8 path"));
+        let status = prover.verify("nonsense statement");
+
+        match status {
+            ProofStatus::Error(message) => assert!(message.contains("illegal begin of vernac")),
+            other => panic!("expected Error, got {other:?}"),
+        }
+    }
+
+    /// A mock `coqc` whose verdict depends on the `.v` file it's handed:
+    /// `FAIL_MARKER` in the source fails with a tactic-failure message,
+    /// anything else succeeds. Used to prove that concurrent `verify`
+    /// calls never see each other's temp files.
+    fn write_content_sensitive_mock_coqc(dir: &std::path::Path) -> std::path::PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join("coqc.sh");
+        fs::write(
+            &path,
+            "#!/bin/sh\nif grep -q FAIL_MARKER \"$1\"; then\n  echo 'tactic failure' 1>&2\n  exit 1\nfi\nexit 0\n",
+        )
+        .expect("write mock coqc script");
+        fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).expect("make script executable");
+        path
+    }
+
+    #[test]
+    fn coq_prover_verify_is_safe_under_concurrent_invocations() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let script = write_content_sensitive_mock_coqc(dir.path());
+        let prover = CoqProver::new(script.to_str().expect("utf8 path"));
+
+        std::thread::scope(|scope| {
+            for i in 0..8 {
+                let prover = &prover;
+                scope.spawn(move || {
+                    if i % 2 == 0 {
+                        let statement = format!("n{i} = n{i}");
+                        assert_eq!(prover.verify(&statement), ProofStatus::Proved);
+                    } else {
+                        let statement = format!("FAIL_MARKER n{i} = n{i}");
+                        assert_eq!(prover.verify(&statement), ProofStatus::Disproved);
+                    }
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn coq_prover_auto_detect_returns_none_when_nothing_is_installed_at_the_known_paths() {
+        // None of the hardcoded candidate paths are expected to exist in a
+        // typical CI/dev sandbox, so this should reliably come back empty.
+        // If it doesn't, `coqc` really is installed at one of those paths
+        // and the prover should indeed pick it up.
+        if Path::new("/usr/bin/coqc").exists()
+            || Path::new("/usr/local/bin/coqc").exists()
+        {
+            return;
+        }
+        assert!(CoqProver::auto_detect().is_none());
+    }
+
+    #[test]
+    fn formula_renders_reflexivity_as_an_smtlib2_query() {
+        let formula = Formula::ForAll("x".to_string(), Box::new(Formula::Eq(Term::Var("x".to_string()), Term::Var("x".to_string()))));
+        assert_eq!(formula.to_smtlib2(), "(forall ((x Int)) (= x x))");
+    }
+
+    #[test]
+    fn formula_renders_reflexivity_as_lean_text() {
+        let formula = Formula::ForAll("x".to_string(), Box::new(Formula::Eq(Term::Var("x".to_string()), Term::Var("x".to_string()))));
+        assert_eq!(formula.to_lean(), "forall x, x = x");
+    }
+
+    #[test]
+    fn z3_external_prover_renders_verify_formula_as_an_smtlib2_assertion() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let script = dir.path().join("echo-statement.sh");
+        let output_file = dir.path().join("statement.txt");
+        fs::write(
+            &script,
+            format!("#!/bin/sh\nprintf '%s' \"$1\" > {:?}\nexit 0\n", output_file),
+        )
+        .expect("write mock prover script");
+        fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).expect("make script executable");
+
+        let prover = ExternalProver::new("z3", script.to_str().expect("utf8 path"));
+        let formula = Formula::ForAll("x".to_string(), Box::new(Formula::Eq(Term::Var("x".to_string()), Term::Var("x".to_string()))));
+        let status = prover.verify_formula(&formula);
+        assert_eq!(status, ProofStatus::Proved);
+
+        let contents = fs::read_to_string(&output_file).expect("script wrote its statement");
+        assert_eq!(contents, "(assert (forall ((x Int)) (= x x)))\n(check-sat)");
+    }
+
+    #[test]
+    fn non_z3_external_prover_falls_back_to_lean_text_for_verify_formula() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let script = dir.path().join("echo-statement.sh");
+        let output_file = dir.path().join("statement.txt");
+        fs::write(&script, format!("#!/bin/sh\nprintf '%s' \"$1\" > {:?}\nexit 0\n", output_file))
+            .expect("write mock prover script");
+        fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).expect("make script executable");
+
+        let prover = ExternalProver::new("cvc5", script.to_str().expect("utf8 path"));
+        let formula = Formula::Gt(Term::Var("x".to_string()), Term::Int(0));
+        assert_eq!(prover.verify_formula(&formula), ProofStatus::Proved);
+
+        let contents = fs::read_to_string(&output_file).expect("script wrote its statement");
+        assert_eq!(contents, "x > 0");
+    }
+
+    #[test]
+    fn z3_reports_quantifier_support_unlike_a_restricted_prover() {
+        let z3 = ExternalProver::new("z3", "z3");
+        let restricted = MockProver::new("restricted", ProofStatus::Proved);
+
+        assert!(z3.capabilities().supports_quantifiers);
+        assert!(!restricted.capabilities().supports_quantifiers);
+        assert_ne!(z3.capabilities(), restricted.capabilities());
+    }
+
+    #[test]
+    fn lean_and_coq_report_full_first_order_capabilities() {
+        let lean = LeanProver::new("lean");
+        let coq = CoqProver::new("/usr/bin/coqc");
+
+        for capabilities in [lean.capabilities(), coq.capabilities()] {
+            assert_eq!(capabilities.logics, vec![Logic::FullFirstOrder]);
+            assert!(capabilities.supports_quantifiers);
+            assert_eq!(capabilities.speed, SpeedClass::Slow);
+        }
+    }
+}
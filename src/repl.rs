@@ -0,0 +1,260 @@
+//! Suggested repo path: src/repl.rs
+//!
+//! Interactive REPL commands for poking at a `MemoryGraph` directly,
+//! outside of a full Kurisu dialogue session.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use chrono::Utc;
+
+use crate::memory_graph::{MemoryGraph, SymbolicNode, SymbolicRelation};
+
+/// How many undo snapshots to keep around, to bound memory use.
+const MAX_UNDO_DEPTH: usize = 20;
+
+/// Parse a REPL `add` line's arguments (everything after the `add` token)
+/// into a `SymbolicNode`: `<key> <framework> <confidence> <content...>`.
+/// Pulled out as its own function so both the interactive REPL and batch
+/// mode can reuse it.
+pub fn parse_add_line(args: &str) -> Result<SymbolicNode, String> {
+    let mut parts = args.splitn(4, ' ');
+    let key = parts.next().filter(|s| !s.is_empty()).ok_or("add requires a key")?;
+    let framework = parts.next().filter(|s| !s.is_empty()).ok_or("add requires a framework")?;
+    let confidence_str = parts.next().ok_or("add requires a confidence value")?;
+    let content = parts.next().ok_or("add requires content")?;
+
+    let confidence: f32 = confidence_str
+        .parse()
+        .map_err(|_| format!("confidence '{confidence_str}' is not a number"))?;
+
+    Ok(SymbolicNode {
+        key: key.to_string(),
+        content: content.to_string(),
+        confidence,
+        framework: framework.to_string(),
+        last_updated: Utc::now(),
+        provenance: None,
+        metadata: HashMap::new(),
+    })
+}
+
+pub struct Repl {
+    pub graph: MemoryGraph,
+    undo_stack: Vec<MemoryGraph>,
+}
+
+impl Repl {
+    pub fn new(graph: MemoryGraph) -> Self {
+        Self {
+            graph,
+            undo_stack: Vec::new(),
+        }
+    }
+
+    /// Push a snapshot of the current graph onto the (bounded) undo stack.
+    fn push_snapshot(&mut self) {
+        self.undo_stack.push(self.graph.snapshot());
+        if self.undo_stack.len() > MAX_UNDO_DEPTH {
+            self.undo_stack.remove(0);
+        }
+    }
+
+    pub fn add(&mut self, node: SymbolicNode) {
+        self.push_snapshot();
+        self.graph.add_concept(node);
+    }
+
+    pub fn delete(&mut self, key: &str) -> Option<SymbolicNode> {
+        self.push_snapshot();
+        self.graph.delete_concept(key)
+    }
+
+    /// Like `delete`, but also removes every relationship that named
+    /// `key`, returning how many of those were dropped alongside it.
+    pub fn remove(&mut self, key: &str) -> (Option<SymbolicNode>, usize) {
+        self.push_snapshot();
+        self.graph.remove_concept(key)
+    }
+
+    pub fn relate(&mut self, relation: SymbolicRelation) -> Result<(), String> {
+        self.push_snapshot();
+        match self.graph.add_relationship(relation) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                // Nothing changed, so don't leave a no-op snapshot behind.
+                self.undo_stack.pop();
+                Err(e)
+            }
+        }
+    }
+
+    pub fn update(&mut self, node: SymbolicNode) {
+        self.push_snapshot();
+        self.graph.add_concept(node);
+    }
+
+    /// Restore the graph to its state before the last mutating command.
+    /// Returns an error if there's nothing to undo.
+    pub fn undo(&mut self) -> Result<(), String> {
+        let previous = self.undo_stack.pop().ok_or("nothing to undo")?;
+        self.graph.restore(previous);
+        Ok(())
+    }
+
+    /// `stats` - a human-readable digest of `MemoryGraph::stats`: concept
+    /// and relationship counts, the framework and relation-type
+    /// breakdowns, and the confidence distribution. Prints zeros for an
+    /// empty graph rather than dividing by zero, since `confidence_
+    /// distribution` already guards that case.
+    pub fn stats(&self) -> String {
+        let stats = self.graph.stats();
+        let mut lines = vec![
+            format!("concepts: {}", stats.concept_count),
+            format!("relationships: {}", stats.relationship_count),
+            "frameworks:".to_string(),
+        ];
+        for (name, count) in &stats.frameworks {
+            lines.push(format!("  {name}: {count}"));
+        }
+        lines.push("relation types:".to_string());
+        for (name, count) in &stats.relation_types {
+            lines.push(format!("  {name}: {count}"));
+        }
+        lines.push(format!(
+            "confidence: min={:.2} mean={:.2} median={:.2} max={:.2}",
+            stats.confidence.min, stats.confidence.mean, stats.confidence.median, stats.confidence.max
+        ));
+        lines.join("\n")
+    }
+
+    /// `dot [path]` - with no path, return the DOT rendering of the graph
+    /// so the caller can print it; with a path, write it to that file
+    /// instead.
+    pub fn dot(&self, path: Option<&Path>) -> Result<Option<String>, String> {
+        let dot = self.graph.to_dot();
+        match path {
+            None => Ok(Some(dot)),
+            Some(path) => {
+                fs::write(path, dot).map_err(|e| format!("failed to write DOT file: {e}"))?;
+                Ok(None)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory_graph::{SymbolicNode, SymbolicRelation};
+    use chrono::Utc;
+    use tempfile::tempdir;
+
+    fn sample_repl() -> Repl {
+        let mut graph = MemoryGraph::new();
+        graph.add_concept(SymbolicNode {
+            key: "kurisu".to_string(),
+            content: "neuroscientist".to_string(),
+            confidence: 0.9,
+            framework: "biography".to_string(),
+            last_updated: Utc::now(),
+            provenance: None,
+            metadata: HashMap::new(),
+        });
+        graph.add_concept(SymbolicNode {
+            key: "okabe".to_string(),
+            content: "self-proclaimed mad scientist".to_string(),
+            confidence: 0.9,
+            framework: "biography".to_string(),
+            last_updated: Utc::now(),
+            provenance: None,
+            metadata: HashMap::new(),
+        });
+        graph.relationships.push(SymbolicRelation {
+            from: "okabe".to_string(),
+            to: "kurisu".to_string(),
+            relation_type: "trusts".to_string(),
+            strength: 0.8,
+            last_updated: Utc::now(),
+        });
+        Repl::new(graph)
+    }
+
+    #[test]
+    fn dot_with_no_path_returns_digraph_text() {
+        let repl = sample_repl();
+        let output = repl.dot(None).expect("dot succeeds").expect("text returned");
+        assert!(output.contains("digraph"));
+        assert!(output.contains("kurisu"));
+        assert!(output.contains("okabe"));
+    }
+
+    #[test]
+    fn parse_add_line_builds_a_symbolic_node() {
+        let node = parse_add_line("iit-1 IIT 0.8 consciousness requires integration")
+            .expect("well-formed add line");
+        assert_eq!(node.key, "iit-1");
+        assert_eq!(node.framework, "IIT");
+        assert_eq!(node.confidence, 0.8);
+        assert_eq!(node.content, "consciousness requires integration");
+    }
+
+    #[test]
+    fn parse_add_line_rejects_missing_fields() {
+        assert!(parse_add_line("iit-1 IIT").is_err());
+    }
+
+    #[test]
+    fn parse_add_line_rejects_bad_confidence() {
+        assert!(parse_add_line("iit-1 IIT not-a-number some content").is_err());
+    }
+
+    #[test]
+    fn undo_restores_a_deleted_concept() {
+        let mut repl = sample_repl();
+        assert!(repl.graph.get_concept("kurisu").is_some());
+
+        repl.delete("kurisu");
+        assert!(repl.graph.get_concept("kurisu").is_none());
+
+        repl.undo().expect("there is a snapshot to restore");
+        assert!(repl.graph.get_concept("kurisu").is_some());
+    }
+
+    #[test]
+    fn undo_with_empty_stack_errors() {
+        let mut repl = sample_repl();
+        assert!(repl.undo().is_err());
+    }
+
+    #[test]
+    fn stats_reports_concept_count_and_frameworks() {
+        let repl = sample_repl();
+        let output = repl.stats();
+        assert!(output.contains("concepts: 2"));
+        assert!(output.contains("biography"));
+    }
+
+    #[test]
+    fn stats_on_an_empty_graph_prints_zeros() {
+        let repl = Repl::new(MemoryGraph::new());
+        let output = repl.stats();
+        assert!(output.contains("concepts: 0"));
+        assert!(output.contains("relationships: 0"));
+        assert!(output.contains("min=0.00"));
+    }
+
+    #[test]
+    fn dot_with_path_writes_file() {
+        let repl = sample_repl();
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("graph.dot");
+        let result = repl.dot(Some(&path)).expect("dot succeeds");
+        assert!(result.is_none());
+
+        let written = fs::read_to_string(&path).expect("file was written");
+        assert!(written.contains("digraph"));
+        assert!(written.contains("kurisu"));
+    }
+}
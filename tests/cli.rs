@@ -0,0 +1,170 @@
+//! Suggested repo path: tests/cli.rs
+//!
+//! Integration tests for the line-oriented CLI in `src/main.rs`, driven
+//! as a child process via `assert_cmd`.
+
+use assert_cmd::Command;
+
+#[test]
+fn relate_command_connects_two_added_concepts() {
+    let output = Command::cargo_bin("fractal_amadeus")
+        .expect("binary is built")
+        .write_stdin(
+            "add okabe biography 0.9 self-proclaimed mad scientist\n\
+             add kurisu biography 0.9 neuroscientist\n\
+             relate okabe|kurisu|trusts|0.8\n",
+        )
+        .output()
+        .expect("cli runs to completion");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("added concept 'okabe'"));
+    assert!(stdout.contains("added concept 'kurisu'"));
+    assert!(stdout.contains("related 'okabe' to 'kurisu' (trusts)"));
+}
+
+#[test]
+fn relate_command_accepts_an_omitted_strength() {
+    let output = Command::cargo_bin("fractal_amadeus")
+        .expect("binary is built")
+        .write_stdin(
+            "add okabe biography 0.9 self-proclaimed mad scientist\n\
+             add kurisu biography 0.9 neuroscientist\n\
+             relate okabe|kurisu|trusts\n",
+        )
+        .output()
+        .expect("cli runs to completion");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("related 'okabe' to 'kurisu' (trusts)"));
+}
+
+#[test]
+fn relate_command_reports_a_non_numeric_strength() {
+    let output = Command::cargo_bin("fractal_amadeus")
+        .expect("binary is built")
+        .write_stdin("relate okabe|kurisu|trusts|not-a-number\n")
+        .output()
+        .expect("cli runs to completion");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("error: strength 'not-a-number' is not a number"));
+}
+
+#[test]
+fn save_then_load_in_a_separate_process_recovers_the_concept() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let graph_path = dir.path().join("graph.json");
+
+    let save_output = Command::cargo_bin("fractal_amadeus")
+        .expect("binary is built")
+        .write_stdin(format!(
+            "add kurisu biography 0.9 neuroscientist\nsave {}\n",
+            graph_path.display()
+        ))
+        .output()
+        .expect("cli runs to completion");
+    assert!(save_output.status.success());
+    assert!(String::from_utf8_lossy(&save_output.stdout).contains(&format!("saved to '{}'", graph_path.display())));
+
+    let load_output = Command::cargo_bin("fractal_amadeus")
+        .expect("binary is built")
+        .write_stdin(format!("load {}\nget kurisu\n", graph_path.display()))
+        .output()
+        .expect("cli runs to completion");
+    assert!(load_output.status.success());
+    let stdout = String::from_utf8_lossy(&load_output.stdout);
+    assert!(stdout.contains(&format!("loaded from '{}'", graph_path.display())));
+    assert!(stdout.contains("kurisu: neuroscientist"));
+}
+
+#[test]
+fn list_command_shows_both_added_concepts() {
+    let output = Command::cargo_bin("fractal_amadeus")
+        .expect("binary is built")
+        .write_stdin(
+            "add okabe biography 0.9 self-proclaimed mad scientist\n\
+             add kurisu biography 0.9 neuroscientist\n\
+             list\n",
+        )
+        .output()
+        .expect("cli runs to completion");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("okabe (biography, confidence: 0.90)"));
+    assert!(stdout.contains("kurisu (biography, confidence: 0.90)"));
+}
+
+#[test]
+fn script_mode_runs_commands_from_a_file() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let script_path = dir.path().join("commands.txt");
+    std::fs::write(&script_path, "add kurisu biography 0.9 neuroscientist\nget kurisu\n").expect("write script");
+
+    let output = Command::cargo_bin("fractal_amadeus")
+        .expect("binary is built")
+        .arg("--script")
+        .arg(&script_path)
+        .output()
+        .expect("cli runs to completion");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("added concept 'kurisu'"));
+    assert!(stdout.contains("kurisu: neuroscientist"));
+}
+
+#[test]
+fn script_mode_with_strict_exits_nonzero_on_the_first_error() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let script_path = dir.path().join("commands.txt");
+    std::fs::write(&script_path, "get missing\nadd kurisu biography 0.9 neuroscientist\n").expect("write script");
+
+    let output = Command::cargo_bin("fractal_amadeus")
+        .expect("binary is built")
+        .arg("--script")
+        .arg(&script_path)
+        .arg("--strict")
+        .output()
+        .expect("cli runs to completion");
+
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("error: no concept named 'missing'"));
+    assert!(!stdout.contains("added concept"), "strict mode should stop before later commands run");
+}
+
+#[test]
+fn delete_command_removes_a_concept_and_reports_the_not_found_message_afterward() {
+    let output = Command::cargo_bin("fractal_amadeus")
+        .expect("binary is built")
+        .write_stdin(
+            "add kurisu biography 0.9 neuroscientist\n\
+             delete kurisu\n\
+             get kurisu\n",
+        )
+        .output()
+        .expect("cli runs to completion");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("deleted concept 'kurisu' (0 relationship(s) removed)"));
+    assert!(stdout.contains("error: no concept named 'kurisu'"));
+}
+
+#[test]
+fn help_command_lists_relate() {
+    let output = Command::cargo_bin("fractal_amadeus")
+        .expect("binary is built")
+        .write_stdin("help\n")
+        .output()
+        .expect("cli runs to completion");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("relate <from>|<to>|<relation_type>|<strength>"));
+}
@@ -0,0 +1,17 @@
+//! Suggested repo path: tests/full_pipeline_example.rs
+//!
+//! Smoke test confirming the `full_pipeline` example still builds and
+//! runs to completion. `assert_cmd` only knows how to locate binaries
+//! (`Command::cargo_bin`), not examples, so this shells out to `cargo run
+//! --example` directly instead.
+
+use std::process::Command;
+
+#[test]
+fn full_pipeline_example_runs_to_completion() {
+    let status = Command::new(env!("CARGO"))
+        .args(["run", "--example", "full_pipeline"])
+        .status()
+        .expect("cargo runs to completion");
+    assert!(status.success());
+}